@@ -14,6 +14,6 @@ END:VCARD"#;
     let mut it = iter(input, true);
     print!("{}", it.next().unwrap()?);
     print!("{}", it.next().unwrap()?);
-    assert!(matches!(it.next(), None));
+    assert!(it.next().is_none());
     Ok(())
 }
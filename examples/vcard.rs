@@ -1,7 +1,8 @@
-use vcard4::Vcard;
+use vcard4::{property::TextListProperty, Vcard};
 
 pub fn main() {
     let mut card = Vcard::new(String::from("John Doe"));
-    card.nickname.push(String::from("Johnny").into());
+    card.nickname
+        .push(TextListProperty::new_comma(vec![String::from("Johnny")]));
     print!("{}", card);
 }
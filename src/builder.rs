@@ -1,8 +1,12 @@
 //! Builder for creating vCards.
 //!
 use crate::{
-    property::{DeliveryAddress, Gender, Kind, TextListProperty},
-    Vcard,
+    parameter::{AddressType, TelephoneType, TypeParameter},
+    property::{
+        AddressProperty, DeliveryAddress, Gender, Kind, Organization,
+        StructuredName, TextListProperty, TextOrUriProperty, TextProperty,
+    },
+    Result, Vcard,
 };
 use time::{Date, OffsetDateTime};
 use uriparse::uri::URI as Uri;
@@ -16,9 +20,9 @@ use language_tags::LanguageTag;
 /// if you need to assign parameters or use a group then either use
 /// [Vcard](Vcard) directly or update properties after finishing a builder.
 ///
-/// The card is not validated so it is possible to create
+/// When finishing the card is not validated so it is possible to create
 /// invalid vCards using the builder. To ensure you have a valid vCard call
-/// [validate](Vcard::validate) afterwards.
+/// [build](VcardBuilder::build) instead.
 ///
 /// The builder does not support the CLIENTPIDMAP property, if you need to
 /// use a CLIENTPIDMAP use [Vcard](Vcard).
@@ -67,14 +71,26 @@ impl VcardBuilder {
     /// Should be family name, given name, additional names, honorific
     /// prefixes followed by honorific suffixes.
     pub fn name(mut self, value: [String; 5]) -> Self {
-        self.card.name =
-            Some(TextListProperty::new_semi_colon(value.to_vec()));
+        let [family, given, additional, prefix, suffix] =
+            value.map(|v| if v.is_empty() { vec![] } else { vec![v] });
+        self.card.name = Some(
+            StructuredName {
+                family_names: family,
+                given_names: given,
+                additional_names: additional,
+                honorific_prefixes: prefix,
+                honorific_suffixes: suffix,
+            }
+            .into(),
+        );
         self
     }
 
     /// Add a nickname to the vCard.
     pub fn nickname(mut self, value: String) -> Self {
-        self.card.nickname.push(value.into());
+        self.card
+            .nickname
+            .push(TextListProperty::new_comma(vec![value]));
         self
     }
 
@@ -124,6 +140,25 @@ impl VcardBuilder {
         self
     }
 
+    /// Add a telephone number with types to the vCard.
+    pub fn telephone_with_types(
+        mut self,
+        value: String,
+        types: &[TelephoneType],
+    ) -> Self {
+        let mut property: TextProperty = value.into();
+        let parameters =
+            property.parameters.get_or_insert_with(Default::default);
+        parameters.types = Some(
+            types
+                .iter()
+                .map(|t| TypeParameter::Telephone(t.clone()))
+                .collect(),
+        );
+        self.card.tel.push(TextOrUriProperty::Text(property));
+        self
+    }
+
     /// Add an email address to the vCard.
     pub fn email(mut self, value: String) -> Self {
         self.card.email.push(value.into());
@@ -185,8 +220,8 @@ impl VcardBuilder {
     }
 
     /// Add an organization to the vCard.
-    pub fn org(mut self, value: Vec<String>) -> Self {
-        self.card.org.push(TextListProperty::new_semi_colon(value));
+    pub fn org(mut self, value: Organization) -> Self {
+        self.card.org.push(value.into());
         self
     }
 
@@ -226,6 +261,11 @@ impl VcardBuilder {
         self
     }
 
+    /// Add the product identifier for this library to the vCard.
+    pub fn default_prod_id(self) -> Self {
+        self.prod_id(String::from("-//vcard4-rs//EN"))
+    }
+
     /// Set the revision of the vCard.
     pub fn rev(mut self, value: OffsetDateTime) -> Self {
         self.card.rev = Some(value.into());
@@ -244,6 +284,16 @@ impl VcardBuilder {
         self
     }
 
+    /// Set the UID for the vCard to a `urn:uuid:` URN
+    /// with a random (version 4) UUID.
+    ///
+    /// Only available with the `uuid` feature.
+    #[cfg(feature = "uuid")]
+    pub fn generate_uid(self) -> Self {
+        let urn = uuid::Uuid::new_v4().urn().to_string();
+        self.uid(Uri::try_from(urn.as_str()).unwrap().into_owned())
+    }
+
     /// Add a URL to the vCard.
     pub fn url(mut self, value: Uri<'static>) -> Self {
         self.card.url.push(value.into());
@@ -282,12 +332,114 @@ impl VcardBuilder {
     pub fn finish(self) -> Vcard {
         self.card
     }
+
+    /// Finish building the vCard and validate it.
+    pub fn build(self) -> Result<Vcard> {
+        self.card.validate()?;
+        Ok(self.card)
+    }
+}
+
+/// Build address properties.
+///
+/// Components that are not set are empty in the structured value.
+pub struct AddressBuilder {
+    address: DeliveryAddress,
+    types: Vec<AddressType>,
+}
+
+impl AddressBuilder {
+    /// Create a new address builder.
+    pub fn new() -> Self {
+        Self {
+            address: DeliveryAddress {
+                po_box: None,
+                extended_address: None,
+                street_address: None,
+                locality: None,
+                region: None,
+                postal_code: None,
+                country_name: None,
+            },
+            types: Vec::new(),
+        }
+    }
+
+    /// Set the post office box.
+    pub fn po_box(mut self, value: impl Into<String>) -> Self {
+        self.address.po_box = Some(value.into());
+        self
+    }
+
+    /// Set the extended address (e.g: apartment or suite number).
+    pub fn extended_address(mut self, value: impl Into<String>) -> Self {
+        self.address.extended_address = Some(value.into());
+        self
+    }
+
+    /// Set the street address.
+    pub fn street(mut self, value: impl Into<String>) -> Self {
+        self.address.street_address = Some(value.into());
+        self
+    }
+
+    /// Set the locality (e.g: city).
+    pub fn locality(mut self, value: impl Into<String>) -> Self {
+        self.address.locality = Some(value.into());
+        self
+    }
+
+    /// Set the region (e.g: state or province).
+    pub fn region(mut self, value: impl Into<String>) -> Self {
+        self.address.region = Some(value.into());
+        self
+    }
+
+    /// Set the postal code.
+    pub fn postal_code(mut self, value: impl Into<String>) -> Self {
+        self.address.postal_code = Some(value.into());
+        self
+    }
+
+    /// Set the country name.
+    pub fn country(mut self, value: impl Into<String>) -> Self {
+        self.address.country_name = Some(value.into());
+        self
+    }
+
+    /// Set the TYPE parameter values.
+    pub fn types(mut self, value: &[AddressType]) -> Self {
+        self.types = value.to_vec();
+        self
+    }
+
+    /// Finish building the address property.
+    pub fn finish(self) -> AddressProperty {
+        let mut property: AddressProperty = self.address.into();
+        if !self.types.is_empty() {
+            let params =
+                property.parameters.get_or_insert_with(Default::default);
+            params.types =
+                Some(self.types.into_iter().map(Into::into).collect());
+        }
+        property
+    }
+}
+
+impl Default for AddressBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::VcardBuilder;
-    use crate::property::{DeliveryAddress, Kind, LanguageProperty};
+    use crate::{
+        parameter::TelephoneType,
+        property::{DeliveryAddress, Kind, LanguageProperty, Organization},
+        Error,
+    };
     use time::{Date, Month, OffsetDateTime, Time};
 
     #[test]
@@ -339,7 +491,10 @@ mod tests {
             .timezone("Raleigh/North America".to_owned())
             .geo("geo:37.386013,-122.082932".try_into().unwrap())
             // Organizational
-            .org(vec!["Mock Hospital".to_owned(), "Surgery".to_owned()])
+            .org(Organization {
+                name: Some("Mock Hospital".to_owned()),
+                units: vec!["Surgery".to_owned()],
+            })
             .title("Dr".to_owned())
             .role("Master Surgeon".to_owned())
             .logo("https://example.com/mock.jpeg".try_into().unwrap())
@@ -394,6 +549,61 @@ mod tests {
         assert!(card.validate().is_err());
     }
 
+    #[test]
+    fn builder_default_prod_id() {
+        let card = VcardBuilder::new("Jane Doe".to_owned())
+            .default_prod_id()
+            .finish();
+        let encoded = card.to_string();
+        assert!(encoded.contains("\r\nPRODID:-//vcard4-rs//EN\r\n"));
+
+        let decoded = crate::parse(encoded).unwrap().remove(0);
+        assert_eq!(
+            "-//vcard4-rs//EN",
+            decoded.prod_id.as_ref().unwrap().value
+        );
+    }
+
+    #[test]
+    fn builder_build_member() {
+        let result = VcardBuilder::new("Jane Doe".to_owned())
+            .kind(Kind::Individual)
+            .member("https://example.com/foo".try_into().unwrap())
+            .build();
+        assert!(matches!(result, Err(Error::MemberRequiresGroup)));
+
+        let result = VcardBuilder::new("Mock Company".to_owned())
+            .kind(Kind::Group)
+            .member(
+                "urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af"
+                    .try_into()
+                    .unwrap(),
+            )
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn builder_build() {
+        let card = VcardBuilder::new("Jane Doe".to_owned())
+            .email("janedoe@example.com".to_owned())
+            .telephone_with_types(
+                "+10987654321".to_owned(),
+                &[TelephoneType::Cell, TelephoneType::Voice],
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nTEL;TYPE=cell,voice:+10987654321\r\nEMAIL:janedoe@example.com\r\nEND:VCARD\r\n",
+            &card.to_string()
+        );
+
+        let result = VcardBuilder::new("Mock Company".to_owned())
+            .member("https://example.com/bar".try_into().unwrap())
+            .build();
+        assert!(matches!(result, Err(Error::MemberRequiresGroup)));
+    }
+
     #[cfg(not(feature = "language-tags"))]
     #[test]
     fn builder_language() {
@@ -402,7 +612,7 @@ mod tests {
             .lang("fr".to_owned())
             .finish();
         assert_eq!(
-            card.lang.get(0).unwrap(),
+            card.lang.first().unwrap(),
             &LanguageProperty {
                 value: "en".to_owned(),
                 group: None,
@@ -428,7 +638,7 @@ mod tests {
             .lang("fr".parse::<LanguageTag>().unwrap())
             .finish();
         assert_eq!(
-            card.lang.get(0).unwrap(),
+            card.lang.first().unwrap(),
             &LanguageProperty {
                 value: "en".parse::<LanguageTag>().unwrap(),
                 group: None,
@@ -444,4 +654,23 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn builder_generate_uid() {
+        let card = VcardBuilder::new("Jane Doe".to_owned())
+            .generate_uid()
+            .finish();
+        let uid = card.uid.as_ref().unwrap().to_string();
+        assert!(uid.starts_with("urn:uuid:"));
+        let uuid = card.uuid().unwrap();
+        assert_eq!(Some(uuid::Version::Random), uuid.get_version());
+        assert_ne!(
+            card.uid,
+            VcardBuilder::new("Jane Doe".to_owned())
+                .generate_uid()
+                .finish()
+                .uid
+        );
+    }
 }
@@ -0,0 +1,103 @@
+//! Changes between two vCards.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{parameter::Parameters, property::PropertyKind, Vcard};
+
+/// Property that was added, removed or modified.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PropertyChange {
+    /// Kind of the property.
+    pub kind: PropertyKind,
+    /// Name of the property.
+    pub name: String,
+    /// Group for the property.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub group: Option<String>,
+    /// Parameters for the property.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parameters: Option<Parameters>,
+    /// Value of the property.
+    pub value: String,
+}
+
+/// Changes between two vCards.
+///
+/// Multi-valued properties are compared as sets so a changed
+/// value is reported as a removal and an addition; single-valued
+/// properties that differ are reported as modified.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VcardDiff {
+    /// Properties only in the other vCard.
+    pub added: Vec<PropertyChange>,
+    /// Properties only in this vCard.
+    pub removed: Vec<PropertyChange>,
+    /// Single-valued properties as the old and new values.
+    pub modified: Vec<(PropertyChange, PropertyChange)>,
+}
+
+impl VcardDiff {
+    /// Determine if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+    }
+}
+
+/// Compute the changes from one vCard to another.
+pub(crate) fn diff(old: &Vcard, new: &Vcard) -> VcardDiff {
+    let old_props = changes(old);
+    let new_props = changes(new);
+    let mut diff = VcardDiff::default();
+
+    for prop in &old_props {
+        if new_props.contains(prop) {
+            continue;
+        }
+        let replaced = new_props.iter().find(|other| {
+            prop.kind.is_singleton() && other.kind == prop.kind
+        });
+        if let Some(other) = replaced {
+            diff.modified.push((prop.clone(), other.clone()));
+        } else {
+            diff.removed.push(prop.clone());
+        }
+    }
+
+    for prop in new_props {
+        let modified = prop.kind.is_singleton()
+            && old_props.iter().any(|other| other.kind == prop.kind);
+        if !modified && !old_props.contains(&prop) {
+            diff.added.push(prop);
+        }
+    }
+
+    diff
+}
+
+/// Get the properties of a vCard in the order they are written.
+fn changes(card: &Vcard) -> Vec<PropertyChange> {
+    card.property_order()
+        .into_iter()
+        .filter_map(|(kind, index)| {
+            let (name, prop) = card.property(kind, index)?;
+            Some(PropertyChange {
+                kind,
+                name: name.to_string(),
+                group: prop.group().cloned(),
+                parameters: prop.parameters().cloned(),
+                value: prop.to_string(),
+            })
+        })
+        .collect()
+}
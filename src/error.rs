@@ -2,12 +2,33 @@ use thiserror::Error;
 
 /// Error lexing a vcard string.
 #[derive(Debug, Error, PartialEq, Clone, Default)]
-#[doc(hidden)]
 pub enum LexError {
     /// Generic lex error.
     #[default]
     #[error("vcard lex error")]
     Other,
+
+    /// Error generated when a control character is encountered.
+    ///
+    /// The offset is in bytes from the start of the name or
+    /// value that contains the character, the same as the
+    /// offset of `Error::ControlCharacter`.
+    #[error("unexpected control character {0:?} at offset {1}")]
+    UnexpectedControlChar(char, usize),
+
+    /// Error generated when a quoted value is not closed.
+    ///
+    /// The offset of the opening quote is in bytes from the start
+    /// of the parameter value, the same as the offset of
+    /// `Error::ControlCharacter`.
+    #[error("unterminated quote at offset {0}")]
+    UnterminatedQuote(usize),
+
+    /// Error generated when a stream is not valid UTF-8.
+    ///
+    /// The offset is in bytes from the start of the stream.
+    #[error("invalid UTF-8 at offset {0}")]
+    InvalidUtf8(usize),
 }
 
 /// Errors generated by the vCard library.
@@ -24,8 +45,18 @@ pub enum Error {
     VersionMisplaced,
 
     /// Error generated when a control character is encountered.
-    #[error("control characters are not allowed, got '{0}'")]
-    ControlCharacter(String),
+    #[error(
+        "control characters are not allowed, got '{value}' in {name} at offset {offset}"
+    )]
+    ControlCharacter {
+        /// The escaped control character.
+        value: String,
+        /// Name of the property.
+        name: String,
+        /// Byte offset from the start of the property or
+        /// parameter value.
+        offset: usize,
+    },
 
     /// Error generated when an expected token is of the wrong type.
     #[error("input token '{0}' was incorrect")]
@@ -55,6 +86,30 @@ pub enum Error {
     #[error("delivery address '{0}' is invalid")]
     InvalidAddress(String),
 
+    /// Error generated when a group name contains characters other
+    /// than letters, digits and hyphens.
+    #[error("group name '{0}' is invalid")]
+    InvalidGroup(String),
+
+    /// Error generated when a structured name (`N`) is invalid.
+    #[error("structured name '{0}' is invalid")]
+    InvalidName(String),
+
+    /// Error generated when a geo URI (`GEO`) is invalid.
+    #[error("geo uri '{0}' is invalid")]
+    InvalidGeoUri(String),
+
+    /// Error generated when a SORT-AS parameter is specified on a
+    /// property other than N or ORG.
+    #[error(
+        "parameter SORT-AS can only be applied to N or ORG but used on '{0}'"
+    )]
+    InvalidSortAs(String),
+
+    /// Error generated when a CHARSET parameter is not UTF-8.
+    #[error("charset '{0}' is not supported, must be UTF-8")]
+    CharsetParameter(String),
+
     /// Error generated when a LABEL parameter is specified on a property
     /// other than ADR.
     #[error("parameter LABEL can only be applied to ADR but used on '{0}'")]
@@ -80,6 +135,10 @@ pub enum Error {
     #[error("related type value '{0}' is not supported")]
     UnknownRelatedType(String),
 
+    /// Error generated when a TYPE for an ADR property is not supported.
+    #[error("address type value '{0}' is not supported")]
+    UnknownAddressType(String),
+
     /// Error generated when a TYPE for a TEL property is not supported.
     #[error("telephone type value '{0}' is not supported")]
     UnknownTelephoneType(String),
@@ -88,6 +147,52 @@ pub enum Error {
     #[error("value '{0}' is not supported in this context '{1}'")]
     UnsupportedValueType(String, String),
 
+    /// Error generated when a VERSION is not supported.
+    #[error("version '{0}' is not supported")]
+    UnsupportedVersion(String),
+
+    /// Error generated when merging vCards with different UIDs.
+    #[error("vcards can only be merged when the UID values match")]
+    UidMismatch,
+
+    /// Error generated when a property cannot be represented
+    /// in the target version.
+    #[error("property '{0}' cannot be represented in this version")]
+    Unrepresentable(String),
+
+    /// Error generated when a vCard in a stream is not terminated
+    /// by `END:VCARD`.
+    #[error("vcard at index {0} is missing END:VCARD")]
+    UnterminatedCard(usize),
+
+    /// Error generated when there is content outside of the
+    /// `BEGIN:VCARD` and `END:VCARD` framing in a stream.
+    #[error("unexpected content before vcard at index {0}")]
+    UnexpectedContent(usize),
+
+    /// Error generated when a vCard in a stream is invalid.
+    #[error("vcard at index {index} is invalid: {source}")]
+    InvalidCard {
+        /// Index of the vCard in the stream.
+        index: usize,
+        /// Error for the vCard.
+        source: Box<Error>,
+    },
+
+    /// Error generated for a property at a position in the source.
+    ///
+    /// Lines and columns start at one and refer to the physical
+    /// lines of the input before folded lines are joined.
+    #[error("line {line}, column {column}: {source}")]
+    At {
+        /// Line number.
+        line: usize,
+        /// Column number in characters.
+        column: usize,
+        /// Error for the property.
+        source: Box<Error>,
+    },
+
     /// Error generated when a KIND is not supported.
     #[error("kind '{0}' is not supported")]
     UnknownKind(String),
@@ -139,6 +244,10 @@ pub enum Error {
     #[error("PID parameter not allowed for CLIENTPIDMAP")]
     ClientPidMapPidNotAllowed,
 
+    /// Error generated when a jCard value is not valid.
+    #[error("jcard is invalid: {0}")]
+    InvalidJCard(String),
+
     /// Errors generated by the language tags library.
     #[cfg(feature = "language-tags")]
     #[error(transparent)]
@@ -181,7 +290,19 @@ pub enum Error {
     #[error(transparent)]
     Base64(#[from] base64::DecodeError),
 
+    /// Error generated reading from a stream.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     /// Error generated during lexing.
     #[error(transparent)]
     LexError(#[from] LexError),
 }
+
+/// Warning generated when parsing in lenient mode.
+///
+/// Wraps the error that would have been returned when
+/// parsing strictly.
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct Warning(#[from] pub Error);
@@ -5,7 +5,10 @@ use time::{
     Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
 };
 
-use crate::{property::DateAndOrTime, Error, Result};
+use crate::{
+    property::{DateAndOrTime, TimePrecision},
+    split_unescaped, Error, Result,
+};
 
 // UTC OFFSET
 
@@ -17,12 +20,13 @@ pub fn parse_utc_offset(value: &str) -> Result<UtcOffset> {
 
     //println!("Parsing value {}", value);
 
-    let offset_format = format_description::parse(
+    let offset_format = format_description::parse_borrowed::<1>(
         "[offset_hour sign:mandatory][offset_minute]",
     )?;
 
-    let offset_hours =
-        format_description::parse("[offset_hour sign:mandatory]")?;
+    let offset_hours = format_description::parse_borrowed::<1>(
+        "[offset_hour sign:mandatory]",
+    )?;
 
     if let Ok(result) = UtcOffset::parse(value, &offset_format) {
         Ok(result)
@@ -35,7 +39,7 @@ pub(crate) fn format_utc_offset(
     f: &mut fmt::Formatter<'_>,
     val: &UtcOffset,
 ) -> fmt::Result {
-    let offset = format_description::parse(
+    let offset = format_description::parse_borrowed::<1>(
         "[offset_hour sign:mandatory][offset_minute]",
     )
     .map_err(|_| fmt::Error)?;
@@ -47,7 +51,7 @@ pub(crate) fn format_utc_offset(
 /// Parse a list of times separated by a comma.
 pub fn parse_time_list(value: &str) -> Result<Vec<(Time, UtcOffset)>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(parse_time(value)?);
     }
     Ok(values)
@@ -101,8 +105,12 @@ fn do_parse_time(mut value: &str) -> Result<(Time, UtcOffset)> {
 
 pub(crate) fn format_time(value: &(Time, UtcOffset)) -> Result<String> {
     let (time, offset) = value;
-    let format = format_description::parse("[hour][minute][second]")?;
-    let offset_format = format_description::parse(
+    let format =
+        format_description::parse_borrowed::<1>("[hour][minute][second]")?;
+    if *offset == UtcOffset::UTC {
+        return Ok(format!("{}Z", time.format(&format)?));
+    }
+    let offset_format = format_description::parse_borrowed::<1>(
         "[offset_hour sign:mandatory][offset_minute]",
     )?;
 
@@ -132,7 +140,7 @@ pub(crate) fn format_time_list(
 /// Parse a list of dates separated by a comma.
 pub fn parse_date_list(value: &str) -> Result<Vec<Date>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(parse_date(value)?);
     }
     Ok(values)
@@ -176,12 +184,15 @@ pub fn parse_date(value: &str) -> Result<Date> {
 }
 
 fn do_parse_date(s: &str) -> Result<Date> {
-    let date_separator = format_description::parse("[year]-[month]-[day]")?;
-    let date = format_description::parse("[year][month][day]")?;
+    let date_separator =
+        format_description::parse_borrowed::<1>("[year]-[month]-[day]")?;
+    let date = format_description::parse_borrowed::<1>("[year][month][day]")?;
 
-    let year_month_separator = format_description::parse("[year]-[month]")?;
+    let year_month_separator =
+        format_description::parse_borrowed::<1>("[year]-[month]")?;
 
-    let year_month = format_description::parse("[year][month]")?;
+    let year_month =
+        format_description::parse_borrowed::<1>("[year][month]")?;
 
     if let Ok(result) = Date::parse(s, &date_separator) {
         Ok(result)
@@ -197,7 +208,7 @@ fn do_parse_date(s: &str) -> Result<Date> {
 }
 
 pub(crate) fn format_date(value: &Date) -> Result<String> {
-    let date = format_description::parse("[year][month][day]")?;
+    let date = format_description::parse_borrowed::<1>("[year][month][day]")?;
     Ok(value.format(&date)?)
 }
 
@@ -219,7 +230,7 @@ pub(crate) fn format_date_list(
 /// Parse a list of date times separated by a comma.
 pub fn parse_date_time_list(value: &str) -> Result<Vec<OffsetDateTime>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(parse_date_time(value)?);
     }
     Ok(values)
@@ -245,15 +256,19 @@ pub fn parse_date_time(value: &str) -> Result<OffsetDateTime> {
     Ok(utc)
 }
 
+/// Format a date and time.
+///
+/// The `date-time` value type allows truncated forms but
+/// the time is always written in full to avoid ambiguity.
 pub(crate) fn format_date_time(d: &OffsetDateTime) -> Result<String> {
     let offset = (*d).offset();
 
     let format = if offset == UtcOffset::UTC {
-        format_description::parse(
+        format_description::parse_borrowed::<1>(
             "[year][month][day]T[hour][minute][second]Z",
         )?
     } else {
-        format_description::parse(
+        format_description::parse_borrowed::<1>(
             "[year][month][day]T[hour][minute][second][offset_hour sign:mandatory][offset_minute]",
         )?
     };
@@ -261,6 +276,23 @@ pub(crate) fn format_date_time(d: &OffsetDateTime) -> Result<String> {
     Ok(d.format(&format)?)
 }
 
+/// Format a date and time with reduced accuracy.
+///
+/// The time is truncated after the hour or minute so the value
+/// is written with the precision it was parsed with.
+pub(crate) fn format_reduced_date_time(
+    d: &OffsetDateTime,
+    precision: TimePrecision,
+) -> Result<String> {
+    let value = format_date_time(d)?;
+    let hour = value.find('T').unwrap_or_default() + 1;
+    let end = match precision {
+        TimePrecision::Hour => hour + 2,
+        TimePrecision::Minute => hour + 4,
+    };
+    Ok(format!("{}{}", &value[..end], &value[hour + 6..]))
+}
+
 pub(crate) fn format_date_time_list(
     f: &mut fmt::Formatter<'_>,
     val: &[OffsetDateTime],
@@ -278,16 +310,16 @@ pub(crate) fn format_date_time_list(
 
 /// Parse a timestamp.
 pub fn parse_timestamp(value: &str) -> Result<OffsetDateTime> {
-    let offset_format = format_description::parse(
+    let offset_format = format_description::parse_borrowed::<1>(
             "[year][month][day]T[hour][minute][second][offset_hour sign:mandatory][offset_minute]",
         )?;
-    let offset_format_hours = format_description::parse(
+    let offset_format_hours = format_description::parse_borrowed::<1>(
             "[year][month][day]T[hour][minute][second][offset_hour sign:mandatory]",
         )?;
-    let utc_format = format_description::parse(
+    let utc_format = format_description::parse_borrowed::<1>(
         "[year][month][day]T[hour][minute][second]Z",
     )?;
-    let implicit_utc_format = format_description::parse(
+    let implicit_utc_format = format_description::parse_borrowed::<1>(
         "[year][month][day]T[hour][minute][second]",
     )?;
 
@@ -301,18 +333,37 @@ pub fn parse_timestamp(value: &str) -> Result<OffsetDateTime> {
         let result = OffsetDateTime::now_utc().replace_date_time(result);
         Ok(result)
     } else {
-        let result = PrimitiveDateTime::parse(value, &implicit_utc_format)?;
+        let result = PrimitiveDateTime::parse(value, &implicit_utc_format)
+            .map_err(|_| Error::InvalidDateTime(value.to_owned()))?;
         let result = OffsetDateTime::now_utc().replace_date_time(result);
         Ok(result)
     }
 }
 
+/// Format a timestamp.
+///
+/// A `timestamp` is always a complete date and time in the basic
+/// format, for example `20231231T235959Z` or `20231231T235959-0500`.
+pub(crate) fn format_timestamp(d: &OffsetDateTime) -> Result<String> {
+    let format = if d.offset() == UtcOffset::UTC {
+        format_description::parse_borrowed::<1>(
+            "[year repr:full][month][day]T[hour repr:24][minute][second]Z",
+        )?
+    } else {
+        format_description::parse_borrowed::<1>(
+            "[year repr:full][month][day]T[hour repr:24][minute][second][offset_hour sign:mandatory][offset_minute]",
+        )?
+    };
+
+    Ok(d.format(&format)?)
+}
+
 pub(crate) fn format_timestamp_list(
     f: &mut fmt::Formatter<'_>,
     val: &[OffsetDateTime],
 ) -> fmt::Result {
     for (index, item) in val.iter().enumerate() {
-        write!(f, "{}", &format_date_time(item).map_err(|_| fmt::Error)?)?;
+        write!(f, "{}", &format_timestamp(item).map_err(|_| fmt::Error)?)?;
         if index < val.len() - 1 {
             write!(f, ",")?;
         }
@@ -323,7 +374,7 @@ pub(crate) fn format_timestamp_list(
 /// Parse a list of date and or time types possibly separated by a comma.
 pub fn parse_timestamp_list(value: &str) -> Result<Vec<OffsetDateTime>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(parse_timestamp(value)?);
     }
     Ok(values)
@@ -336,7 +387,7 @@ pub fn parse_date_and_or_time_list(
     value: &str,
 ) -> Result<Vec<DateAndOrTime>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(value.parse()?);
     }
     Ok(values)
@@ -367,10 +418,12 @@ pub fn parse_boolean(value: &str) -> Result<bool> {
     }
 }
 
-/// Parse a list of integers.
+/// Parse a comma-delimited list of integers.
+///
+/// An escaped comma is not treated as a delimiter.
 pub fn parse_integer_list(value: &str) -> Result<Vec<i64>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(value.parse()?);
     }
     Ok(values)
@@ -389,10 +442,12 @@ pub(crate) fn format_integer_list(
     Ok(())
 }
 
-/// Parse a list of floats.
+/// Parse a comma-delimited list of floats.
+///
+/// An escaped comma is not treated as a delimiter.
 pub fn parse_float_list(value: &str) -> Result<Vec<f64>> {
     let mut values = Vec::new();
-    for value in value.split(',') {
+    for value in split_unescaped(value, ',') {
         values.push(value.parse()?);
     }
     Ok(values)
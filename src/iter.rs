@@ -1,37 +1,51 @@
 //! Iterator for parsing vCards.
 use crate::{
-    parser::{Token, VcardParser},
+    error::LexError,
+    name::BOM,
+    parser::{unfold_with_folds, Token, VcardParser},
     Error, Result, Vcard,
 };
-use std::ops::Range;
+use std::{borrow::Cow, io::BufRead, ops::Range};
 
 /// Iterator for parsing vCards.
 pub struct VcardIterator<'s> {
-    parser: VcardParser<'s>,
+    source: Cow<'s, str>,
+    folds: Vec<usize>,
+    strict: bool,
     offset: usize,
 }
 
 impl<'s> VcardIterator<'s> {
     /// Create a new iterator.
     pub fn new(source: &'s str, strict: bool) -> Self {
+        let (source, folds) = unfold_with_folds(source);
         Self {
-            parser: VcardParser::new(source, strict),
+            source,
+            folds,
+            strict,
             offset: 0,
         }
     }
 
     /// Parse the next vCard.
-    fn parse_next(&self, offset: usize) -> Result<(Vcard, Range<usize>)> {
-        let mut lex = self.parser.lexer();
+    ///
+    /// Returns `None` when only line breaks remain.
+    fn parse_next(
+        &self,
+        offset: usize,
+    ) -> Result<Option<(Vcard, Range<usize>)>> {
+        let mut parser = VcardParser::new(&self.source, self.strict)
+            .with_folds(&self.folds, 1);
+        let mut lex = parser.lexer();
         lex.bump(offset);
         while let Some(first) = lex.next() {
-            if first == Ok(Token::NewLine) {
+            if matches!(first, Ok(Token::NewLine | Token::ByteOrderMark)) {
                 continue;
             } else {
-                return self.parser.parse_one(&mut lex, Some(first));
+                return parser.parse_one(&mut lex, Some(first)).map(Some);
             }
         }
-        Err(Error::TokenExpected)
+        Ok(None)
     }
 }
 
@@ -39,15 +53,205 @@ impl<'s> Iterator for VcardIterator<'s> {
     type Item = Result<Vcard>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.offset >= self.parser.source.len() {
+        if self.offset >= self.source.len() {
             return None;
         }
         match self.parse_next(self.offset) {
-            Ok((card, span)) => {
+            Ok(Some((card, span))) => {
                 self.offset = span.end;
                 Some(Ok(card))
             }
+            Ok(None) => {
+                self.offset = self.source.len();
+                None
+            }
             Err(e) => Some(Err(e)),
         }
     }
 }
+
+/// Iterator for parsing vCards from a reader.
+///
+/// Lines are unfolded as they are read and only the lines for
+/// the current vCard are buffered.
+pub struct VcardReader<R> {
+    reader: R,
+    pending: Option<String>,
+    restart: Option<(String, Vec<usize>, usize)>,
+    line: usize,
+    offset: usize,
+    skip_lf: bool,
+    index: usize,
+    done: bool,
+}
+
+/// Source for a single vCard read from a stream.
+struct CardSource {
+    source: String,
+    folds: Vec<usize>,
+    first_line: usize,
+}
+
+impl<R: BufRead> VcardReader<R> {
+    /// Create a new reader iterator.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            pending: None,
+            restart: None,
+            line: 0,
+            offset: 0,
+            skip_lf: false,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Read a physical line without the line break.
+    ///
+    /// Lines may end with CRLF, a bare LF or a bare CR.
+    fn read_line(&mut self) -> Result<Option<String>> {
+        let mut bytes = Vec::new();
+        let mut length = 0;
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            if self.skip_lf {
+                self.skip_lf = false;
+                if buffer[0] == b'\n' {
+                    self.reader.consume(1);
+                    self.offset += 1;
+                    continue;
+                }
+            }
+            match buffer.iter().position(|b| matches!(b, b'\r' | b'\n')) {
+                Some(pos) => {
+                    bytes.extend_from_slice(&buffer[..pos]);
+                    self.skip_lf = buffer[pos] == b'\r';
+                    self.reader.consume(pos + 1);
+                    length += pos + 1;
+                    break;
+                }
+                None => {
+                    let amount = buffer.len();
+                    bytes.extend_from_slice(buffer);
+                    self.reader.consume(amount);
+                    length += amount;
+                }
+            }
+        }
+        if length == 0 {
+            return Ok(None);
+        }
+        let line = String::from_utf8(bytes).map_err(|e| {
+            LexError::InvalidUtf8(self.offset + e.utf8_error().valid_up_to())
+        })?;
+        self.offset += length;
+        self.line += 1;
+        Ok(Some(line))
+    }
+
+    /// Read a logical line joining any folded lines.
+    ///
+    /// Returns the line, the offsets where folded lines were
+    /// joined and the number of the first physical line.
+    fn read_unfolded(
+        &mut self,
+    ) -> Result<Option<(String, Vec<usize>, usize)>> {
+        let mut line = match self.pending.take() {
+            Some(line) => line,
+            None => match self.read_line()? {
+                Some(line) => line,
+                None => return Ok(None),
+            },
+        };
+        let number = self.line;
+        let mut folds = Vec::new();
+        while let Some(next) = self.read_line()? {
+            if next.starts_with([' ', '\t']) {
+                folds.push(line.len());
+                line.push_str(&next[1..]);
+            } else {
+                self.pending = Some(next);
+                break;
+            }
+        }
+        Ok(Some((line, folds, number)))
+    }
+
+    /// Read the source for the next vCard.
+    fn read_card(&mut self) -> Result<Option<CardSource>> {
+        let mut card: Option<CardSource> = None;
+        loop {
+            let (line, folds, number) = match self.restart.take() {
+                Some(next) => next,
+                None => match self.read_unfolded()? {
+                    Some(next) => next,
+                    None => break,
+                },
+            };
+            let content = line.trim_start_matches(BOM).trim_end();
+            if content.eq_ignore_ascii_case("BEGIN:VCARD") {
+                if card.is_some() {
+                    self.restart = Some((line, folds, number));
+                    return Err(Error::UnterminatedCard(self.index));
+                }
+                card = Some(CardSource {
+                    source: line,
+                    folds,
+                    first_line: number,
+                });
+            } else if let Some(current) = card.as_mut() {
+                let end = content.eq_ignore_ascii_case("END:VCARD");
+                current.source.push('\n');
+                let offset = current.source.len();
+                current.folds.extend(folds.iter().map(|fold| fold + offset));
+                current.source.push_str(&line);
+                if end {
+                    return Ok(card);
+                }
+            } else if !content.is_empty() {
+                return Err(Error::UnexpectedContent(self.index));
+            }
+        }
+        match card {
+            Some(_) => Err(Error::UnterminatedCard(self.index)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for VcardReader<R> {
+    type Item = Result<Vcard>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let card = match self.read_card() {
+            Ok(Some(card)) => card,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                match e {
+                    Error::Io(_) | Error::LexError(_) => self.done = true,
+                    Error::UnterminatedCard(_) => self.index += 1,
+                    _ => {}
+                }
+                return Some(Err(e));
+            }
+        };
+        let mut parser = VcardParser::new(&card.source, true)
+            .with_folds(&card.folds, card.first_line);
+        let result = parser.parse_first().map_err(|e| Error::InvalidCard {
+            index: self.index,
+            source: Box::new(e),
+        });
+        self.index += 1;
+        Some(result)
+    }
+}
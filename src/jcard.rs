@@ -0,0 +1,761 @@
+//! Conversion to and from the JSON representation of
+//! a vCard (RFC 7095).
+use serde_json::{Map, Value};
+use time::{Date, OffsetDateTime, Time, UtcOffset};
+
+use crate::{
+    encode_parameter, escape_value,
+    name::*,
+    parameter::{Parameters, TimeZoneParameter, ValueType},
+    parser::{valid_group, VcardParser},
+    property::*,
+    Error, Result, Vcard,
+};
+
+const VCARD: &str = "vcard";
+const GROUP: &str = "group";
+
+/// Value type and values of a property in jCard.
+trait JCardValue: Property {
+    /// Get the default value type and the values for the property.
+    fn jcard_value(&self) -> (ValueType, Vec<Value>);
+}
+
+/// Convert a vCard to jCard.
+pub(crate) fn to_jcard(card: &Vcard) -> Value {
+    let mut props = vec![Value::Array(vec![
+        VERSION.to_lowercase().into(),
+        Value::Object(Map::new()),
+        ValueType::Text.to_string().into(),
+        "4.0".into(),
+    ])];
+
+    // General
+    for val in &card.source {
+        props.push(property(val, SOURCE));
+    }
+    if let Some(val) = &card.kind {
+        props.push(property(val, KIND));
+    }
+    for val in &card.xml {
+        props.push(property(val, XML));
+    }
+
+    // Identification
+    for val in &card.formatted_name {
+        props.push(property(val, FN));
+    }
+    if let Some(val) = &card.name {
+        props.push(property(val, N));
+    }
+    for val in &card.nickname {
+        props.push(property(val, NICKNAME));
+    }
+    for val in &card.photo {
+        props.push(property(val, PHOTO));
+    }
+    if let Some(val) = &card.bday {
+        props.push(property(val, BDAY));
+    }
+    if let Some(val) = &card.anniversary {
+        props.push(property(val, ANNIVERSARY));
+    }
+    if let Some(val) = &card.gender {
+        props.push(property(val, GENDER));
+    }
+    for val in &card.url {
+        props.push(property(val, URL));
+    }
+
+    // Delivery Addressing
+    for val in &card.address {
+        props.push(property(val, ADR));
+    }
+
+    // Organizational
+    for val in &card.title {
+        props.push(property(val, TITLE));
+    }
+    for val in &card.role {
+        props.push(property(val, ROLE));
+    }
+    for val in &card.logo {
+        props.push(property(val, LOGO));
+    }
+    for val in &card.org {
+        props.push(property(val, ORG));
+    }
+    for val in &card.member {
+        props.push(property(val, MEMBER));
+    }
+    for val in &card.related {
+        props.push(property(val, RELATED));
+    }
+
+    // Communications
+    for val in &card.tel {
+        props.push(property(val, TEL));
+    }
+    for val in &card.email {
+        props.push(property(val, EMAIL));
+    }
+    for val in &card.impp {
+        props.push(property(val, IMPP));
+    }
+    for val in &card.lang {
+        props.push(property(val, LANG));
+    }
+
+    // Geographic
+    for val in &card.timezone {
+        props.push(property(val, TZ));
+    }
+    for val in &card.geo {
+        props.push(property(val, GEO));
+    }
+
+    // Explanatory
+    for val in &card.categories {
+        props.push(property(val, CATEGORIES));
+    }
+    for val in &card.note {
+        props.push(property(val, NOTE));
+    }
+    if let Some(val) = &card.prod_id {
+        props.push(property(val, PRODID));
+    }
+    if let Some(val) = &card.rev {
+        props.push(property(val, REV));
+    }
+    for val in &card.sound {
+        props.push(property(val, SOUND));
+    }
+    if let Some(val) = &card.uid {
+        props.push(property(val, UID));
+    }
+    for val in &card.client_pid_map {
+        props.push(property(val, CLIENTPIDMAP));
+    }
+
+    // Security
+    for val in &card.key {
+        props.push(property(val, KEY));
+    }
+
+    // Calendar
+    for val in &card.fburl {
+        props.push(property(val, FBURL));
+    }
+    for val in &card.cal_adr_uri {
+        props.push(property(val, CALADRURI));
+    }
+    for val in &card.cal_uri {
+        props.push(property(val, CALURI));
+    }
+
+    // Private property extensions
+    for val in &card.extensions {
+        props.push(property(val, &val.name));
+    }
+
+    Value::Array(vec![VCARD.into(), Value::Array(props)])
+}
+
+/// Convert a property to a jCard property array.
+///
+/// The VALUE parameter, when present, determines the type
+/// otherwise the default type for the property is used.
+fn property(prop: &impl JCardValue, name: &str) -> Value {
+    let (value_type, values) = prop.jcard_value();
+    let value_type = prop
+        .parameters()
+        .and_then(|params| params.value.as_ref())
+        .unwrap_or(&value_type);
+
+    let mut params = prop.parameters().map(parameters).unwrap_or_default();
+    if let Some(group) = prop.group() {
+        params.insert(GROUP.to_string(), group.clone().into());
+    }
+
+    let mut result = vec![
+        name.to_lowercase().into(),
+        Value::Object(params),
+        value_type.to_string().into(),
+    ];
+    result.extend(values);
+    Value::Array(result)
+}
+
+/// Convert property parameters to a jCard parameters object.
+fn parameters(params: &Parameters) -> Map<String, Value> {
+    let mut map = Map::new();
+    if let Some(language) = &params.language {
+        map.insert(LANGUAGE.to_lowercase(), language.to_string().into());
+    }
+    if let Some(pref) = &params.pref {
+        map.insert(PREF.to_lowercase(), pref.to_string().into());
+    }
+    if let Some(alt_id) = &params.alt_id {
+        map.insert(ALTID.to_lowercase(), alt_id.clone().into());
+    }
+    if let Some(pids) = &params.pid {
+        map.insert(PID.to_lowercase(), list(pids));
+    }
+    if let Some(types) = &params.types {
+        map.insert(TYPE.to_lowercase(), list(types));
+    }
+    if let Some(media_type) = &params.media_type {
+        map.insert(MEDIATYPE.to_lowercase(), media_type.to_string().into());
+    }
+    if let Some(calscale) = &params.calscale {
+        map.insert(CALSCALE.to_lowercase(), calscale.clone().into());
+    }
+    if let Some(sort_as) = &params.sort_as {
+        map.insert(SORT_AS.to_lowercase(), list(sort_as));
+    }
+    if let Some(geo) = &params.geo {
+        map.insert(GEO.to_lowercase(), geo.to_string().into());
+    }
+    if let Some(tz) = &params.timezone {
+        let value = match tz {
+            TimeZoneParameter::Text(val) => val.clone(),
+            TimeZoneParameter::Uri(val) => val.to_string(),
+            TimeZoneParameter::UtcOffset(val) => utc_offset(val),
+        };
+        map.insert(TZ.to_lowercase(), value.into());
+    }
+    if let Some(label) = &params.label {
+        map.insert(LABEL.to_lowercase(), label.clone().into());
+    }
+    if let Some(extensions) = &params.extensions {
+        for (name, values) in extensions {
+            map.insert(name.to_lowercase(), list(values));
+        }
+    }
+    map
+}
+
+/// Parameter value which is a string for a single
+/// item otherwise an array.
+fn list(items: &[impl ToString]) -> Value {
+    if items.len() == 1 {
+        items[0].to_string().into()
+    } else {
+        Value::Array(items.iter().map(|v| v.to_string().into()).collect())
+    }
+}
+
+fn date(value: &Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        value.year(),
+        value.month() as u8,
+        value.day()
+    )
+}
+
+fn time(value: &Time) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        value.hour(),
+        value.minute(),
+        value.second()
+    )
+}
+
+fn utc_offset(value: &UtcOffset) -> String {
+    let (h, m, _) = value.as_hms();
+    let sign = if value.is_negative() { '-' } else { '+' };
+    format!("{}{:02}:{:02}", sign, h.abs(), m.abs())
+}
+
+/// Time zone designator for a date time or time.
+fn zone(value: &UtcOffset) -> String {
+    if value.is_utc() {
+        "Z".to_string()
+    } else {
+        utc_offset(value)
+    }
+}
+
+fn date_time(value: &OffsetDateTime) -> String {
+    format!(
+        "{}T{}{}",
+        date(&value.date()),
+        time(&value.time()),
+        zone(&value.offset())
+    )
+}
+
+fn partial_date(value: &PartialDate) -> String {
+    match (value.year(), value.month(), value.day()) {
+        (None, Some(month), Some(day)) => {
+            format!("--{:02}-{:02}", month, day)
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn date_and_or_time(value: &DateAndOrTime) -> String {
+    match value {
+        DateAndOrTime::Date(val) => date(val),
+        DateAndOrTime::PartialDate(val) => partial_date(val),
+        DateAndOrTime::DateTime(val) => date_time(val),
+        DateAndOrTime::ReducedDateTime(val, precision) => {
+            let time = match precision {
+                TimePrecision::Hour => format!("{:02}", val.hour()),
+                TimePrecision::Minute => {
+                    format!("{:02}:{:02}", val.hour(), val.minute())
+                }
+            };
+            format!("{}T{}{}", date(&val.date()), time, zone(&val.offset()))
+        }
+        DateAndOrTime::Time((val, offset)) => {
+            format!("T{}{}", time(val), zone(offset))
+        }
+    }
+}
+
+fn strings(values: &[String]) -> Value {
+    Value::Array(values.iter().map(|v| v.clone().into()).collect())
+}
+
+impl JCardValue for TextProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        (ValueType::Text, vec![self.value.clone().into()])
+    }
+}
+
+impl JCardValue for TextListProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let values = match self.delimiter {
+            // Structured value
+            TextListDelimiter::SemiColon => vec![strings(&self.value)],
+            // Multiple values
+            TextListDelimiter::Comma => {
+                self.value.iter().map(|v| v.clone().into()).collect()
+            }
+        };
+        (ValueType::Text, values)
+    }
+}
+
+impl JCardValue for NameProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let components = self
+            .value
+            .components()
+            .iter()
+            .map(|component| match component.len() {
+                0 => Value::String(String::new()),
+                1 => component[0].clone().into(),
+                _ => strings(component),
+            })
+            .collect();
+        (ValueType::Text, vec![Value::Array(components)])
+    }
+}
+
+impl JCardValue for OrgProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let components = self
+            .value
+            .components()
+            .into_iter()
+            .map(|component| component.into())
+            .collect();
+        (ValueType::Text, vec![Value::Array(components)])
+    }
+}
+
+impl JCardValue for AddressProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let components = [
+            &self.value.po_box,
+            &self.value.extended_address,
+            &self.value.street_address,
+            &self.value.locality,
+            &self.value.region,
+            &self.value.postal_code,
+            &self.value.country_name,
+        ]
+        .iter()
+        .map(|v| v.as_deref().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+        (ValueType::Text, vec![strings(&components)])
+    }
+}
+
+impl JCardValue for UriProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        (ValueType::Uri, vec![self.value.to_string().into()])
+    }
+}
+
+impl JCardValue for KindProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        (ValueType::Text, vec![self.value.to_string().into()])
+    }
+}
+
+impl JCardValue for GenderProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let sex = self.value.sex.to_string();
+        let value = if let Some(identity) = &self.value.identity {
+            strings(&[sex, identity.clone()])
+        } else {
+            sex.into()
+        };
+        (ValueType::Text, vec![value])
+    }
+}
+
+impl JCardValue for LanguageProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        (ValueType::LanguageTag, vec![self.value.to_string().into()])
+    }
+}
+
+impl JCardValue for DateTimeProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        (ValueType::Timestamp, vec![date_time(&self.value).into()])
+    }
+}
+
+impl JCardValue for DateAndOrTimeProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let values = self
+            .value
+            .iter()
+            .map(|v| date_and_or_time(v).into())
+            .collect();
+        (ValueType::DateAndOrTime, values)
+    }
+}
+
+impl JCardValue for UtcOffsetProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        (ValueType::UtcOffset, vec![utc_offset(&self.value).into()])
+    }
+}
+
+impl JCardValue for ClientPidMapProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        let value = Value::Array(vec![
+            self.value.source.into(),
+            self.value.uri.to_string().into(),
+        ]);
+        (ValueType::Text, vec![value])
+    }
+}
+
+impl JCardValue for TextOrUriProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        match self {
+            Self::Text(val) => val.jcard_value(),
+            Self::Uri(val) => val.jcard_value(),
+        }
+    }
+}
+
+impl JCardValue for DateTimeOrTextProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        match self {
+            Self::DateTime(val) => val.jcard_value(),
+            Self::Text(val) => val.jcard_value(),
+        }
+    }
+}
+
+impl JCardValue for TimeZoneProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        match self {
+            Self::Text(val) => val.jcard_value(),
+            Self::Uri(val) => val.jcard_value(),
+            Self::UtcOffset(val) => val.jcard_value(),
+        }
+    }
+}
+
+impl JCardValue for ExtensionProperty {
+    fn jcard_value(&self) -> (ValueType, Vec<Value>) {
+        match &self.value {
+            AnyProperty::Text(val) => {
+                (ValueType::Text, vec![val.clone().into()])
+            }
+            AnyProperty::Integer(val) => (
+                ValueType::Integer,
+                val.iter().map(|v| (*v).into()).collect(),
+            ),
+            AnyProperty::Float(val) => {
+                (ValueType::Float, val.iter().map(|v| (*v).into()).collect())
+            }
+            AnyProperty::Boolean(val) => {
+                (ValueType::Boolean, vec![(*val).into()])
+            }
+            AnyProperty::Date(val) => (
+                ValueType::Date,
+                val.iter().map(|v| date(v).into()).collect(),
+            ),
+            AnyProperty::DateTime(val) => (
+                ValueType::DateTime,
+                val.iter().map(|v| date_time(v).into()).collect(),
+            ),
+            AnyProperty::Time(val) => (
+                ValueType::Time,
+                val.iter()
+                    .map(|(t, o)| format!("{}{}", time(t), zone(o)).into())
+                    .collect(),
+            ),
+            AnyProperty::DateAndOrTime(val) => (
+                ValueType::DateAndOrTime,
+                val.iter().map(|v| date_and_or_time(v).into()).collect(),
+            ),
+            AnyProperty::Timestamp(val) => (
+                ValueType::Timestamp,
+                val.iter().map(|v| date_time(v).into()).collect(),
+            ),
+            AnyProperty::Uri(val) => {
+                (ValueType::Uri, vec![val.to_string().into()])
+            }
+            AnyProperty::UtcOffset(val) => {
+                (ValueType::UtcOffset, vec![utc_offset(val).into()])
+            }
+            AnyProperty::Language(val) => {
+                (ValueType::LanguageTag, vec![val.to_string().into()])
+            }
+        }
+    }
+}
+
+/// Convert jCard to a vCard.
+///
+/// Each property is converted to a content line which is then
+/// parsed so the result is identical to parsing the equivalent
+/// vCard text. A VALUE parameter is only added to a content line
+/// when the type differs from the default type for the property.
+///
+/// Names and groups are validated and parameter values are quoted
+/// and encoded (RFC 6868) so a jCard cannot inject content lines.
+pub(crate) fn from_jcard(value: &Value) -> Result<Vcard> {
+    let items = value
+        .as_array()
+        .filter(|items| items.len() == 2 && items[0] == VCARD)
+        .ok_or_else(|| {
+            Error::InvalidJCard("expected [\"vcard\", [...]]".to_string())
+        })?;
+    let props = items[1].as_array().ok_or_else(|| {
+        Error::InvalidJCard("properties must be an array".to_string())
+    })?;
+
+    let mut source = format!("{}\r\n{}\r\n", BEGIN, VERSION_4);
+    for prop in props {
+        let prop = prop
+            .as_array()
+            .filter(|prop| prop.len() >= 4)
+            .ok_or_else(|| {
+                Error::InvalidJCard(format!(
+                    "property must be an array of at least four items, \
+                    got {}",
+                    prop
+                ))
+            })?;
+        let name = string(&prop[0])?.to_uppercase();
+        if name == VERSION {
+            continue;
+        }
+        // Names share the grammar of group names (iana-token or
+        // x-name) and must not be able to start or end a vCard
+        if !valid_group(&name) || name == "BEGIN" || name == "END" {
+            return Err(Error::InvalidJCard(format!(
+                "invalid property name {}",
+                prop[0]
+            )));
+        }
+        source.push_str(&content_line(
+            &name,
+            &prop[1],
+            &prop[2],
+            &prop[3..],
+        )?);
+        source.push_str("\r\n");
+    }
+    source.push_str(END);
+
+    let mut parser = VcardParser::new(&source, true);
+    parser.parse_first()
+}
+
+/// Convert a jCard property to a content line.
+fn content_line(
+    name: &str,
+    params: &Value,
+    value_type: &Value,
+    values: &[Value],
+) -> Result<String> {
+    let params = params.as_object().ok_or_else(|| {
+        Error::InvalidJCard(format!(
+            "parameters for {} must be an object",
+            name
+        ))
+    })?;
+    let value_type: ValueType = string(value_type)?.parse()?;
+
+    let mut line = String::new();
+    if let Some(group) = params.get(GROUP) {
+        let group = string(group)?;
+        if !valid_group(group) {
+            return Err(Error::InvalidJCard(format!(
+                "invalid group {} for {}",
+                group, name
+            )));
+        }
+        line.push_str(group);
+        line.push('.');
+    }
+    line.push_str(name);
+
+    if default_value_type(name) != value_type {
+        line.push_str(&format!(";{}={}", VALUE, value_type));
+    }
+
+    for (key, value) in params {
+        if key == GROUP {
+            continue;
+        }
+        if !valid_group(key) {
+            return Err(Error::InvalidJCard(format!(
+                "invalid parameter name {} for {}",
+                key, name
+            )));
+        }
+        let key = key.to_uppercase();
+        let items = match value {
+            Value::Array(items) => {
+                items.iter().map(scalar).collect::<Result<Vec<_>>>()?
+            }
+            _ => vec![scalar(value)?],
+        };
+        // A quoted TZ parameter is a URI so other time zone
+        // values are written as is
+        let quote = key != TZ || items.iter().any(|item| item.contains(':'));
+        let items = items
+            .iter()
+            .map(|item| {
+                let item = encode_parameter(item);
+                if quote {
+                    format!("\"{}\"", item)
+                } else {
+                    item.into_owned()
+                }
+            })
+            .collect::<Vec<_>>();
+        line.push_str(&format!(";{}={}", key, items.join(",")));
+    }
+
+    line.push(':');
+    let values = values
+        .iter()
+        .map(|value| property_value(&value_type, value))
+        .collect::<Result<Vec<_>>>()?;
+    line.push_str(&values.join(","));
+    Ok(line)
+}
+
+/// Default value type for a property.
+fn default_value_type(name: &str) -> ValueType {
+    match name {
+        SOURCE | PHOTO | URL | IMPP | GEO | LOGO | MEMBER | RELATED
+        | SOUND | UID | KEY | FBURL | CALADRURI | CALURI => ValueType::Uri,
+        BDAY | ANNIVERSARY => ValueType::DateAndOrTime,
+        LANG => ValueType::LanguageTag,
+        REV => ValueType::Timestamp,
+        _ => ValueType::Text,
+    }
+}
+
+/// Convert a jCard value to the vCard text representation.
+fn property_value(value_type: &ValueType, value: &Value) -> Result<String> {
+    match value {
+        // Structured value
+        Value::Array(components) => Ok(components
+            .iter()
+            .map(|component| match component {
+                // Component with multiple values
+                Value::Array(items) => Ok(items
+                    .iter()
+                    .map(|item| Ok(escape_value(&scalar(item)?, true)))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(",")),
+                _ => Ok(escape_value(&scalar(component)?, true)),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(";")),
+        _ => {
+            let value = scalar(value)?;
+            if value_type != &ValueType::Text && value.contains(['\r', '\n'])
+            {
+                return Err(Error::InvalidJCard(format!(
+                    "line break in {} value",
+                    value_type
+                )));
+            }
+            Ok(match value_type {
+                ValueType::Text => escape_value(&value, false),
+                ValueType::Date
+                | ValueType::Time
+                | ValueType::DateTime
+                | ValueType::DateAndOrTime
+                | ValueType::Timestamp
+                | ValueType::UtcOffset => basic_format(&value),
+                _ => value,
+            })
+        }
+    }
+}
+
+/// Convert a date and or time in the extended format used by jCard
+/// to the basic format used by vCard.
+fn basic_format(value: &str) -> String {
+    let (date, time) = match value.find('T') {
+        Some(index) => value.split_at(index),
+        None if value.contains(':') => ("", value),
+        None => (value, ""),
+    };
+    // A year and month is the same in both formats
+    if time.is_empty()
+        && !date.starts_with('-')
+        && date.len() == 7
+        && date.as_bytes().get(4) == Some(&b'-')
+    {
+        return date.to_string();
+    }
+    // Leading hyphens denote a truncated date
+    let truncated = date.len() - date.trim_start_matches('-').len();
+    format!(
+        "{}{}{}",
+        &date[..truncated],
+        date[truncated..].replace('-', ""),
+        time.replace(':', "")
+    )
+}
+
+/// Get a string from a JSON value.
+fn string(value: &Value) -> Result<&str> {
+    value.as_str().ok_or_else(|| {
+        Error::InvalidJCard(format!("expected a string, got {}", value))
+    })
+}
+
+/// Convert a JSON string, number or boolean to a string.
+fn scalar(value: &Value) -> Result<String> {
+    match value {
+        Value::String(val) => Ok(val.clone()),
+        Value::Number(val) => Ok(val.to_string()),
+        Value::Bool(val) => Ok(val.to_string()),
+        _ => Err(Error::InvalidJCard(format!(
+            "expected a scalar value, got {}",
+            value
+        ))),
+    }
+}
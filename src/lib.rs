@@ -17,6 +17,12 @@
 //! * `LanguageTag` (feature: `language-tags`)
 //! * `Mime` (feature: `mime`)
 //!
+//! The `uriparse` crate does not give access to the buffers owned
+//! by a `Uri` so they cannot be wiped, this includes payloads embedded
+//! in `data:` URIs (eg: PHOTO). Decoding such a payload does not leave
+//! further copies behind and the `DataUri` it returns is zeroized
+//! on drop.
+//!
 //! If the `mime` feature is enabled the MEDIATYPE parameter is parsed
 //! to a `Mime` struct otherwise it is a `String`.
 //!
@@ -24,7 +30,23 @@
 //! and the LANGUAGE parameter are parsed using the
 //! [language-tags](https://docs.rs/language-tags/latest/language_tags/) crate.
 //!
-//! Serde support can be enabled with the `serde` feature.
+//! Version 3.0 vCards ([RFC2426](https://www.rfc-editor.org/rfc/rfc2426))
+//! are also accepted and translated to the 4.0 model where possible,
+//! for example bare TYPE parameters, the AGENT property and the
+//! structured GEO value.
+//!
+//! Serde support can be enabled with the `serde` feature which
+//! also adds conversion to jCard ([RFC7095](https://www.rfc-editor.org/rfc/rfc7095)).
+//!
+//! The `phonenumber` feature adds normalization of telephone
+//! numbers to the E.164 format.
+//!
+//! The `uuid` feature adds access to a UID property value in the
+//! `urn:uuid:` form as a
+//! [Uuid](https://docs.rs/uuid/latest/uuid/struct.Uuid.html).
+//!
+//! The `testing` feature adds a `testing` module so that
+//! downstream crates can check their own fixtures round trip.
 //!
 //! ## Examples
 //!
@@ -79,7 +101,7 @@
 //!     let mut it = iter(input, true);
 //!     print!("{}", it.next().unwrap()?);
 //!     print!("{}", it.next().unwrap()?);
-//!     assert!(matches!(it.next(), None));
+//!     assert!(it.next().is_none());
 //!     Ok(())
 //! }
 //! ```
@@ -90,43 +112,63 @@
 //!   validated as it is optional in the RFC.
 //! * IANA Tokens are not implemented.
 //! * The RFC requires a CRLF sequence for line breaks but for
-//!   easier interoperability between platforms we treat the
-//!   carriage return as optional.
+//!   easier interoperability between platforms a bare LF or a
+//!   bare CR is also accepted; output always uses CRLF.
+//! * Empty lines between properties are ignored and trailing
+//!   spaces and tabs are removed from property values except for
+//!   free text properties (eg: `FN`, `NOTE`) and extensions where
+//!   the whitespace may be significant.
 //!
 
 mod builder;
+mod diff;
 mod error;
 pub mod helper;
 mod iter;
+#[cfg(feature = "serde")]
+mod jcard;
 mod name;
 pub mod parameter;
 mod parser;
 pub mod property;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod tokenize;
+mod v3;
 mod vcard;
+mod xcard;
 
-pub use builder::VcardBuilder;
-pub use error::Error;
-pub use iter::VcardIterator;
-pub use vcard::Vcard;
+pub use builder::{AddressBuilder, VcardBuilder};
+pub use diff::{PropertyChange, VcardDiff};
+pub use error::{Error, LexError, Warning};
+pub use iter::{VcardIterator, VcardReader};
+pub use parser::ParseOptions;
+pub use tokenize::{tokenize, Token};
+pub use vcard::{Identity, Vcard, Version};
 
 pub use time;
 pub use uriparse;
 
+use std::borrow::Cow;
+
 /// Result type for the vCard library.
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Parse a vCard string into a collection of vCards.
 pub fn parse<S: AsRef<str>>(input: S) -> Result<Vec<Vcard>> {
-    let parser = parser::VcardParser::new(input.as_ref(), true);
+    let (source, folds) = parser::unfold_with_folds(input.as_ref());
+    let mut parser =
+        parser::VcardParser::new(&source, true).with_folds(&folds, 1);
     parser.parse()
 }
 
 /// Parse a vCard string into a collection of vCards ignoring properties
 /// that generate errors.
 pub fn parse_loose<S: AsRef<str>>(input: S) -> Result<Vec<Vcard>> {
-    let parser = parser::VcardParser::new(input.as_ref(), false);
+    let source = parser::unfold(input.as_ref());
+    let mut parser = parser::VcardParser::new(&source, false);
     parser.parse()
 }
 
@@ -151,10 +193,96 @@ pub(crate) fn escape_value(value: &str, semi_colons: bool) -> String {
     }
 }
 
+/// Unescape a property value.
+pub(crate) fn unescape_text(value: &str) -> Cow<'_, str> {
+    if !value.contains('\\') {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(c @ (',' | ';' | '\\')) => result.push(c),
+            Some(c) => {
+                result.push('\\');
+                result.push(c);
+            }
+            None => result.push('\\'),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Split a value on a delimiter that is not escaped.
+pub(crate) fn split_unescaped(value: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            parts.push(&value[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Encode a parameter value using circumflex escaping (RFC 6868).
+pub(crate) fn encode_parameter(value: &str) -> Cow<'_, str> {
+    if !value.contains(['^', '\n', '"']) {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len() + 2);
+    for c in value.chars() {
+        match c {
+            '^' => result.push_str("^^"),
+            '\n' => result.push_str("^n"),
+            '"' => result.push_str("^'"),
+            _ => result.push(c),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Decode the circumflex escaping for a parameter value (RFC 6868).
+///
+/// A circumflex followed by any other character is left as is.
+pub(crate) fn decode_parameter(value: &str) -> Cow<'_, str> {
+    if !value.contains('^') {
+        return Cow::Borrowed(value);
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        let decoded = match (c, chars.peek()) {
+            ('^', Some('n')) => '\n',
+            ('^', Some('^')) => '^',
+            ('^', Some('\'')) => '"',
+            _ => {
+                result.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        result.push(decoded);
+    }
+    Cow::Owned(result)
+}
+
 pub(crate) fn unescape_value(value: &str) -> String {
     use aho_corasick::AhoCorasick;
-    let patterns = &["\r", "\n ", "\n\t", "\\n", "\\N", "\\,"];
-    let replace_with = &["", "", "", "\n", "\n", ","];
+    let patterns = &["\r", "\\n", "\\N", "\\,"];
+    let replace_with = &["", "\n", "\n", ","];
     let ac = AhoCorasick::new(patterns);
     ac.replace_all(value, replace_with)
 }
@@ -1,4 +1,4 @@
-/// Constants for property and parameter names.
+//! Constants for property and parameter names.
 
 pub(crate) const HOME: &str = "home";
 pub(crate) const WORK: &str = "work";
@@ -7,6 +7,9 @@ pub(crate) const BEGIN: &str = "BEGIN:VCARD";
 pub(crate) const VERSION_4: &str = "VERSION:4.0";
 pub(crate) const END: &str = "END:VCARD";
 
+/// UTF-8 byte order mark some exporters write before a vCard.
+pub(crate) const BOM: char = '\u{FEFF}';
+
 // Property
 pub(crate) const VERSION: &str = "VERSION";
 pub(crate) const SOURCE: &str = "SOURCE";
@@ -44,6 +47,8 @@ pub(crate) const FBURL: &str = "FBURL";
 pub(crate) const CALADRURI: &str = "CALADRURI";
 pub(crate) const CALURI: &str = "CALURI";
 pub(crate) const XML: &str = "XML";
+// vCard 3.0 only
+pub(crate) const AGENT: &str = "AGENT";
 
 // Parameter
 pub(crate) const LANGUAGE: &str = "LANGUAGE";
@@ -61,3 +66,6 @@ pub(crate) const LABEL: &str = "LABEL";
 
 // Apple uses this for embedded photos
 pub(crate) const ENCODING: &str = "ENCODING";
+
+// vCard 3.0 exports may specify the character set
+pub(crate) const CHARSET: &str = "CHARSET";
@@ -1,6 +1,7 @@
 //! Types for property parameters.
 
 use std::{
+    borrow::Cow,
     fmt::{self, Debug},
     str::FromStr,
 };
@@ -20,38 +21,42 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 use mime::Mime;
 
 use crate::{
+    encode_parameter,
     helper::format_utc_offset,
     name::{HOME, WORK},
     Error, Result,
 };
 
-/// Names of properties that are allowed to specify a TYPE parameter.
-pub(crate) const TYPE_PROPERTIES: [&str; 23] = [
-    "FN",
-    "NICKNAME",
-    "PHOTO",
-    "ADR",
-    "TEL",
-    "EMAIL",
-    "IMPP",
-    "LANG",
-    "TZ",
-    "GEO",
-    "TITLE",
-    "ROLE",
-    "LOGO",
-    "ORG",
-    "RELATED",
-    "CATEGORIES",
-    "NOTE",
-    "SOUND",
-    "URL",
-    "KEY",
-    "FBURL",
-    "CALADRURI",
-    "CALURI",
+/// Names of properties that do not allow a TYPE parameter.
+///
+/// Taken from the property grammars in RFC 6350; note that `FN`
+/// permits TYPE whilst properties such as `BDAY`, `KIND` and `UID`
+/// do not.
+const NO_TYPE_PROPERTIES: [&str; 13] = [
+    "SOURCE",
+    "KIND",
+    "N",
+    "BDAY",
+    "ANNIVERSARY",
+    "GENDER",
+    "MEMBER",
+    "PRODID",
+    "REV",
+    "UID",
+    "CLIENTPIDMAP",
+    "XML",
+    "VERSION",
 ];
 
+/// Determine if a property allows the TYPE parameter.
+///
+/// The name must be upper case; extension properties and
+/// properties that are not known (eg: `SOCIALPROFILE` from
+/// RFC 9554) may always specify a TYPE parameter.
+pub(crate) fn allows_type_parameter(name: &str) -> bool {
+    !NO_TYPE_PROPERTIES.contains(&name)
+}
+
 /// Value for a TYPE parameter.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -67,6 +72,9 @@ pub enum TypeParameter {
     Related(RelatedType),
     /// Extension type parameter specified using the X- syntax.
     Extension(String),
+    /// Type that is not known for the property, written back
+    /// exactly as it was given.
+    Unknown(String),
 }
 
 impl fmt::Display for TypeParameter {
@@ -77,6 +85,7 @@ impl fmt::Display for TypeParameter {
             Self::Telephone(ref tel) => write!(f, "{}", tel),
             Self::Related(ref rel) => write!(f, "{}", rel),
             Self::Extension(ref value) => write!(f, "X-{}", value),
+            Self::Unknown(ref value) => write!(f, "{}", value),
         }
     }
 }
@@ -101,7 +110,7 @@ impl FromStr for TypeParameter {
                         Ok(tel) => Ok(Self::Telephone(tel)),
                         Err(_) => match s.parse::<RelatedType>() {
                             Ok(value) => Ok(Self::Related(value)),
-                            Err(_) => Ok(Self::Extension(s.to_string())),
+                            Err(_) => Ok(Self::Unknown(s.to_string())),
                         },
                     }
                 }
@@ -163,6 +172,45 @@ impl FromStr for Pid {
     }
 }
 
+/// Preference value for a PREF parameter.
+///
+/// Lower values are more preferred, so the ordering of this
+/// type sorts the most preferred value first.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pref(u8);
+
+impl Pref {
+    /// Create a new preference value.
+    ///
+    /// The value must be between 1 and 100 inclusive.
+    pub fn new(value: u8) -> Result<Self> {
+        if !(1..=100).contains(&value) {
+            return Err(Error::PrefOutOfRange(value));
+        }
+        Ok(Self(value))
+    }
+
+    /// Get the preference value.
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl fmt::Display for Pref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Pref {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s.parse()?)
+    }
+}
+
 /// Enumeration of related types.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -271,7 +319,7 @@ impl FromStr for RelatedType {
     }
 }
 
-/// Enumeration of telephone types.
+/// Value for the TYPE parameter of the TEL property.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
@@ -330,6 +378,101 @@ impl FromStr for TelephoneType {
     }
 }
 
+/// Value for the TYPE parameter of the ADR property.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AddressType {
+    /// Home address.
+    Home,
+    /// Work address.
+    Work,
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Home => HOME,
+                Self::Work => WORK,
+            }
+        )
+    }
+}
+
+impl FromStr for AddressType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match &s.to_lowercase()[..] {
+            HOME => Ok(Self::Home),
+            WORK => Ok(Self::Work),
+            _ => Err(Error::UnknownAddressType(s.to_string())),
+        }
+    }
+}
+
+impl From<AddressType> for TypeParameter {
+    fn from(value: AddressType) -> Self {
+        match value {
+            AddressType::Home => Self::Home,
+            AddressType::Work => Self::Work,
+        }
+    }
+}
+
+/// Value for the TYPE parameter shared by most properties.
+///
+/// Any value other than `home` or `work` is retained as text;
+/// the telephone and related types have dedicated enumerations.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CommonType {
+    /// Related to a home environment.
+    Home,
+    /// Related to a work environment.
+    Work,
+    /// Any other type value.
+    Other(String),
+}
+
+impl fmt::Display for CommonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Home => write!(f, "{}", HOME),
+            Self::Work => write!(f, "{}", WORK),
+            Self::Other(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for CommonType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match &s.to_lowercase()[..] {
+            HOME => Ok(Self::Home),
+            WORK => Ok(Self::Work),
+            _ => Ok(Self::Other(s.to_string())),
+        }
+    }
+}
+
+impl From<&TypeParameter> for CommonType {
+    fn from(value: &TypeParameter) -> Self {
+        match value {
+            TypeParameter::Home => Self::Home,
+            TypeParameter::Work => Self::Work,
+            _ => Self::Other(value.to_string()),
+        }
+    }
+}
+
 /// Enumeration of types for the VALUE parameter.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -427,13 +570,31 @@ pub enum TimeZoneParameter {
     Text(String),
     /// Uri value.
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::uri"))]
     Uri(Uri<'static>),
     /// UTC offset value.
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::utc_offset"))]
     UtcOffset(UtcOffset),
 }
 
+impl fmt::Display for TimeZoneParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(val) => write!(f, "{}", val),
+            Self::Uri(val) => write!(f, "{}", val),
+            Self::UtcOffset(val) => format_utc_offset(f, val),
+        }
+    }
+}
+
 /// Parameters for a vCard property.
+///
+/// When written parameters are always in the same order
+/// regardless of the order they were parsed: VALUE first and
+/// then all other parameters, including extension parameters,
+/// in alphabetical order of their names. Extension parameters
+/// with the same name keep the order they were declared.
 #[derive(Debug, Default, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
@@ -522,7 +683,11 @@ pub struct Parameters {
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
     #[cfg_attr(
         feature = "serde",
-        serde(default, skip_serializing_if = "Option::is_none")
+        serde(
+            default,
+            with = "crate::serde::uri_option",
+            skip_serializing_if = "Option::is_none",
+        )
     )]
     pub geo: Option<Uri<'static>>,
     /// The TZ parameter.
@@ -548,71 +713,193 @@ pub struct Parameters {
     pub extensions: Option<Vec<(String, Vec<String>)>>,
 }
 
+impl Parameters {
+    /// Get the property identifiers from the PID parameter.
+    pub fn pids(&self) -> &[Pid] {
+        self.pid.as_deref().unwrap_or_default()
+    }
+
+    /// Get the language from the LANGUAGE parameter.
+    #[cfg(feature = "language-tags")]
+    pub fn language(&self) -> Option<&LanguageTag> {
+        self.language.as_ref()
+    }
+
+    /// Get the language from the LANGUAGE parameter.
+    #[cfg(not(feature = "language-tags"))]
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Get the sort values from the SORT-AS parameter.
+    pub fn sort_as(&self) -> &[String] {
+        self.sort_as.as_deref().unwrap_or_default()
+    }
+
+    /// Get the media type from the MEDIATYPE parameter.
+    #[cfg(feature = "mime")]
+    pub fn media_type(&self) -> Option<&Mime> {
+        self.media_type.as_ref()
+    }
+
+    /// Get the media type from the MEDIATYPE parameter.
+    #[cfg(not(feature = "mime"))]
+    pub fn media_type(&self) -> Option<&str> {
+        self.media_type.as_deref()
+    }
+
+    /// Get the values of a parameter by name.
+    ///
+    /// The name is case-insensitive and list parameters such as
+    /// TYPE, PID and SORT-AS yield one entry for each value. Text
+    /// values are borrowed, other values are formatted.
+    pub fn values(&self, name: &str) -> Vec<Cow<'_, str>> {
+        use crate::name::*;
+        fn list<T: ToString>(values: &Option<Vec<T>>) -> Vec<Cow<'_, str>> {
+            values
+                .iter()
+                .flatten()
+                .map(|value| Cow::Owned(value.to_string()))
+                .collect()
+        }
+        fn single<T: ToString>(value: &Option<T>) -> Vec<Cow<'_, str>> {
+            value
+                .iter()
+                .map(|value| Cow::Owned(value.to_string()))
+                .collect()
+        }
+        fn text<T: AsRef<str>>(value: &Option<T>) -> Vec<Cow<'_, str>> {
+            value
+                .iter()
+                .map(|value| Cow::Borrowed(value.as_ref()))
+                .collect()
+        }
+
+        let upper_name = name.to_uppercase();
+        match &upper_name[..] {
+            #[cfg(feature = "language-tags")]
+            LANGUAGE => self
+                .language
+                .iter()
+                .map(|tag| Cow::Borrowed(tag.as_str()))
+                .collect(),
+            #[cfg(not(feature = "language-tags"))]
+            LANGUAGE => text(&self.language),
+            VALUE => single(&self.value),
+            PREF => single(&self.pref),
+            ALTID => text(&self.alt_id),
+            PID => list(&self.pid),
+            TYPE => list(&self.types),
+            MEDIATYPE => text(&self.media_type),
+            CALSCALE => text(&self.calscale),
+            SORT_AS => self
+                .sort_as
+                .iter()
+                .flatten()
+                .map(|value| Cow::Borrowed(value.as_str()))
+                .collect(),
+            GEO => single(&self.geo),
+            TZ => single(&self.timezone),
+            LABEL => text(&self.label),
+            _ => self
+                .extensions
+                .iter()
+                .flatten()
+                .filter(|(name, _)| name.eq_ignore_ascii_case(&upper_name))
+                .flat_map(|(_, values)| values.iter())
+                .map(|value| Cow::Borrowed(value.as_str()))
+                .collect(),
+        }
+    }
+}
+
 impl fmt::Display for Parameters {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use crate::name::*;
-        if let Some(language) = &self.language {
-            write!(f, ";{}={}", LANGUAGE, language)?;
-        }
         if let Some(value) = &self.value {
             write!(f, ";{}={}", VALUE, value)?;
         }
+
+        let mut params: Vec<(&str, String)> = Vec::new();
+        if let Some(language) = &self.language {
+            params.push((LANGUAGE, language.to_string()));
+        }
         if let Some(pref) = &self.pref {
-            write!(f, ";{}={}", PREF, pref)?;
+            params.push((PREF, pref.to_string()));
         }
         if let Some(alt_id) = &self.alt_id {
-            write!(f, ";{}=\"{}\"", ALTID, alt_id)?;
+            params.push((ALTID, format!("\"{}\"", encode_parameter(alt_id))));
         }
         if let Some(pids) = &self.pid {
-            write!(f, ";{}={}", PID, comma_delimited(pids))?;
+            params.push((PID, comma_delimited(pids)));
         }
         if let Some(types) = &self.types {
-            write!(f, ";{}={}", TYPE, comma_delimited(types))?;
+            params.push((TYPE, comma_delimited(types)));
         }
         if let Some(media_type) = &self.media_type {
-            write!(f, ";{}={}", MEDIATYPE, media_type)?;
+            params.push((MEDIATYPE, media_type.to_string()));
         }
         if let Some(calscale) = &self.calscale {
-            write!(f, ";{}={}", CALSCALE, calscale)?;
+            params.push((CALSCALE, encode_parameter(calscale).into_owned()));
         }
         if let Some(sort_as) = &self.sort_as {
-            write!(f, ";{}=\"{}\"", SORT_AS, comma_delimited(sort_as))?;
+            let sort_as = sort_as
+                .iter()
+                .map(|value| encode_parameter(value))
+                .collect::<Vec<_>>();
+            params.push((
+                SORT_AS,
+                format!("\"{}\"", comma_delimited(&sort_as)),
+            ));
         }
         if let Some(geo) = &self.geo {
-            write!(f, ";{}=\"{}\"", GEO, geo)?;
+            params.push((GEO, format!("\"{}\"", geo)));
         }
         if let Some(tz) = &self.timezone {
-            match tz {
+            let value = match tz {
                 TimeZoneParameter::Text(val) => {
-                    write!(f, ";{}={}", TZ, val)?;
-                }
-                TimeZoneParameter::UtcOffset(val) => {
-                    write!(f, ";{}=", TZ)?;
-                    format_utc_offset(f, val)?;
+                    encode_parameter(val).into_owned()
                 }
+                TimeZoneParameter::UtcOffset(_) => tz.to_string(),
                 // URI must be quoted
-                TimeZoneParameter::Uri(val) => {
-                    write!(f, ";{}=\"{}\"", TZ, val)?;
-                }
-            }
+                TimeZoneParameter::Uri(val) => format!("\"{}\"", val),
+            };
+            params.push((TZ, value));
         }
         if let Some(label) = &self.label {
-            write!(f, ";{}=\"{}\"", LABEL, escape_parameter(label))?;
+            params.push((LABEL, format!("\"{}\"", encode_parameter(label))));
         }
         if let Some(extensions) = &self.extensions {
-            for (name, value) in extensions {
-                write!(f, ";{}=\"{}\"", name, comma_delimited(value))?;
+            for (name, values) in extensions {
+                let values = values
+                    .iter()
+                    .map(|value| {
+                        let value = encode_parameter(value);
+                        if value.contains([',', ';', ':']) {
+                            format!("\"{}\"", value)
+                        } else {
+                            value.into_owned()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                params.push((name, values.join(",")));
             }
         }
+
+        // Stable so repeated extensions keep their order
+        params.sort_by(|(a, _), (b, _)| {
+            let a = a.bytes().map(|c| c.to_ascii_uppercase());
+            let b = b.bytes().map(|c| c.to_ascii_uppercase());
+            a.cmp(b)
+        });
+        for (name, value) in params {
+            write!(f, ";{}={}", name, value)?;
+        }
         Ok(())
     }
 }
 
-fn escape_parameter(s: &str) -> String {
-    s.replace('\n', "\\n")
-}
-
-fn comma_delimited(items: &Vec<impl std::fmt::Display>) -> String {
+fn comma_delimited(items: &[impl std::fmt::Display]) -> String {
     let mut value = String::new();
     for (index, item) in items.iter().enumerate() {
         value.push_str(&item.to_string());
@@ -11,30 +11,37 @@ use language_tags::LanguageTag;
 use mime::Mime;
 
 use crate::{
-    error::LexError, escape_control, helper::*, name::*, parameter::*,
-    property::*, unescape_value, Error, Result, Vcard,
+    decode_parameter, error::LexError, escape_control, helper::*, name::*,
+    parameter::*, property::*, split_unescaped, unescape_text,
+    unescape_value, Error, Result, Vcard, Version, Warning,
 };
 
 type LexResult<T> = std::result::Result<T, LexError>;
 
+/// Names of properties with free text values where trailing
+/// whitespace is retained.
+const TEXT_PROPERTIES: [&str; 11] = [
+    FN, N, NICKNAME, ADR, TITLE, ROLE, ORG, CATEGORIES, NOTE, PRODID, XML,
+];
+
 #[derive(Logos, Debug, PartialEq)]
 #[logos(error = LexError)]
 pub(crate) enum Token {
     #[regex("(?i:BEGIN:VCARD)")]
     Begin,
 
-    #[regex("(?i:VERSION:[3-4]\\.0)")]
+    #[regex("(?i:VERSION:[0-9]+\\.[0-9]+)")]
     Version,
 
     // Special case shared between property and parameter
-    #[token("TZ")]
+    #[regex("(?i:TZ)")]
     TimeZone,
 
     // Special case shared between property and parameter
-    #[token("GEO")]
+    #[regex("(?i:GEO)")]
     Geo,
 
-    #[regex("(?i:([a-z0-9-]+\\.)?(SOURCE|KIND|FN|N|NICKNAME|PHOTO|BDAY|ANNIVERSARY|GENDER|ADR|TEL|EMAIL|IMPP|LANG|TITLE|ROLE|LOGO|ORG|MEMBER|RELATED|CATEGORIES|NOTE|PRODID|REV|SOUND|UID|CLIENTPIDMAP|URL|KEY|FBURL|CALADRURI|CALURI|XML|VERSION|(X-[a-z0-9-]+)))")]
+    #[regex("(?i:([a-z0-9-]+\\.)?(SOURCE|KIND|FN|N|NICKNAME|PHOTO|BDAY|ANNIVERSARY|GENDER|ADR|TEL|EMAIL|IMPP|LANG|TITLE|ROLE|LOGO|ORG|MEMBER|RELATED|CATEGORIES|NOTE|PRODID|REV|SOUND|UID|CLIENTPIDMAP|URL|KEY|FBURL|CALADRURI|CALURI|XML|VERSION|AGENT|(X-[a-z0-9-]+)))")]
     PropertyName,
 
     #[regex("(?i:x-[a-z0-9-]+)")]
@@ -55,9 +62,6 @@ pub(crate) enum Token {
     #[token(":")]
     PropertyDelimiter,
 
-    #[regex("\\r?\\n( |\\t)")]
-    FoldedLine,
-
     #[token("\\,")]
     EscapedComma,
 
@@ -82,36 +86,131 @@ pub(crate) enum Token {
     #[regex("(?i:END:VCARD)")]
     End,
 
+    #[token("\u{FEFF}")]
+    ByteOrderMark,
+
     #[regex("[\u{00}-\u{7F}]", priority = 0)]
     Text,
 }
 
+/// Options for parsing vCards.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Whether to enforce the RFC strictly.
+    ///
+    /// When disabled properties that fail to parse are skipped,
+    /// unknown TYPE values for the TEL and RELATED properties are
+    /// preserved as extension types and a missing FN is tolerated;
+    /// each relaxation is recorded as a [Warning](crate::Warning).
+    pub strict: bool,
+    /// Whether to skip properties that fail to parse.
+    ///
+    /// The line number and error for each skipped property are
+    /// recorded, see [Vcard::parse_skipping](crate::Vcard::parse_skipping),
+    /// and parsing continues with the next property; checks for
+    /// required properties still apply.
+    pub skip_invalid_properties: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            strict: true,
+            skip_invalid_properties: false,
+        }
+    }
+}
+
 /// Parses vCards from a string.
 pub(crate) struct VcardParser<'s> {
-    strict: bool,
-    pub(crate) source: &'s str,
+    options: ParseOptions,
+    source: &'s str,
+    version: Version,
+    warnings: Vec<Warning>,
+    skipped: Vec<(usize, Error)>,
+    folds: &'s [usize],
+    first_line: usize,
 }
 
 impl<'s> VcardParser<'s> {
     /// Create a new parser.
     pub fn new(source: &'s str, strict: bool) -> Self {
-        Self { source, strict }
+        Self::with_options(
+            source,
+            ParseOptions {
+                strict,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a new parser with options.
+    pub fn with_options(source: &'s str, options: ParseOptions) -> Self {
+        Self {
+            source,
+            options,
+            version: Version::V4,
+            warnings: Vec::new(),
+            skipped: Vec::new(),
+            folds: &[],
+            first_line: 1,
+        }
+    }
+
+    /// Set the positions of folded lines removed from the source
+    /// and the line number of the first line of the source so that
+    /// errors report positions in the original input.
+    pub fn with_folds(
+        mut self,
+        folds: &'s [usize],
+        first_line: usize,
+    ) -> Self {
+        self.folds = folds;
+        self.first_line = first_line;
+        self
+    }
+
+    /// Line and column in the original input for an offset into
+    /// the unfolded source.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+        let folds = self.folds.iter().filter(|fold| **fold <= offset);
+        let line = self.first_line + before.matches('\n').count();
+        let line = line + folds.clone().count();
+        // Continuation lines start with the folding whitespace
+        let column = match folds.rev().find(|fold| **fold >= line_start) {
+            Some(fold) => before[*fold..].chars().count() + 2,
+            None => before[line_start..].chars().count() + 1,
+        };
+        (line, column)
+    }
+
+    /// Warnings collected whilst parsing in lenient mode.
+    pub(crate) fn into_warnings(self) -> Vec<Warning> {
+        self.warnings
+    }
+
+    /// Line numbers and errors for properties that were skipped.
+    pub(crate) fn into_skipped(self) -> Vec<(usize, Error)> {
+        self.skipped
     }
 
     /// Parse a UTF-8 encoded string into a list of vCards.
-    pub(crate) fn parse(&self) -> Result<Vec<Vcard>> {
+    pub(crate) fn parse(&mut self) -> Result<Vec<Vcard>> {
         let mut cards = Vec::new();
         let mut lex = self.lexer();
 
         while let Some(first) = lex.next() {
             // Allow leading newlines and newlines between
-            // vCard definitions
-            if first == Ok(Token::NewLine) {
+            // vCard definitions, a byte order mark may also
+            // precede each vCard
+            if matches!(first, Ok(Token::NewLine | Token::ByteOrderMark)) {
                 continue;
             }
 
             let (card, _) = self.parse_one(&mut lex, Some(first))?;
-            card.validate()?;
+            self.validate(&card)?;
             cards.push(card);
         }
 
@@ -122,6 +221,32 @@ impl<'s> VcardParser<'s> {
         Ok(cards)
     }
 
+    /// Parse the first vCard in the source.
+    pub(crate) fn parse_first(&mut self) -> Result<Vcard> {
+        let mut lex = self.lexer();
+        while let Some(first) = lex.next() {
+            if matches!(first, Ok(Token::NewLine | Token::ByteOrderMark)) {
+                continue;
+            }
+
+            let (card, _) = self.parse_one(&mut lex, Some(first))?;
+            self.validate(&card)?;
+            return Ok(card);
+        }
+        Err(Error::TokenExpected)
+    }
+
+    /// Validate a parsed vCard.
+    fn validate(&mut self, card: &Vcard) -> Result<()> {
+        match card.validate() {
+            Err(Error::NoFormattedName) if !self.options.strict => {
+                self.warnings.push(Error::NoFormattedName.into());
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
     /// Get a lexer for the current source.
     pub(crate) fn lexer(&self) -> Lexer<'s, Token> {
         Token::lexer(self.source)
@@ -129,7 +254,7 @@ impl<'s> VcardParser<'s> {
 
     /// Parse a single vCard.
     pub(crate) fn parse_one(
-        &self,
+        &mut self,
         lex: &mut Lexer<'_, Token>,
         first: Option<LexResult<Token>>,
     ) -> Result<(Vcard, Range<usize>)> {
@@ -137,6 +262,8 @@ impl<'s> VcardParser<'s> {
         self.assert_token(lex.next().as_ref(), &[Token::NewLine])?;
 
         self.assert_token(lex.next().as_ref(), &[Token::Version])?;
+        let version = &lex.slice()[VERSION.len() + 1..];
+        self.version = version.parse()?;
         self.assert_token(lex.next().as_ref(), &[Token::NewLine])?;
 
         let mut card: Vcard = Default::default();
@@ -148,7 +275,7 @@ impl<'s> VcardParser<'s> {
 
     /// Parse the properties of a vCard.
     fn parse_properties(
-        &self,
+        &mut self,
         lex: &mut Lexer<'_, Token>,
         card: &mut Vcard,
     ) -> Result<()> {
@@ -156,24 +283,88 @@ impl<'s> VcardParser<'s> {
             if first == Ok(Token::End) {
                 break;
             }
+            // Skip empty lines between properties
+            if first == Ok(Token::NewLine) {
+                continue;
+            }
+            // Concatenated streams may carry a byte order mark
+            // before the end of a vCard
+            if first == Ok(Token::ByteOrderMark)
+                && lex
+                    .remainder()
+                    .get(..END.len())
+                    .is_some_and(|end| end.eq_ignore_ascii_case(END))
+            {
+                continue;
+            }
             if let Ok(Token::Version) = first {
                 return Err(Error::VersionMisplaced);
             }
+            let start = lex.span().start;
+
+            let known = matches!(
+                first,
+                Ok(Token::PropertyName)
+                    | Ok(Token::ExtensionName)
+                    | Ok(Token::TimeZone)
+                    | Ok(Token::Geo)
+            );
+            let name_end = self
+                .name_end(lex, &[';', ':'], true)
+                .filter(|end| !known || lex.span().end != *end);
+
+            let result = if let Some(end) = name_end {
+                // Unknown IANA properties are preserved as extensions
+                let name = &lex.source()[lex.span().start..end];
+                self.skip_to(lex, end)?;
+                let upper_name = name.to_uppercase();
+                let plain_name = upper_name.rsplit('.').next().unwrap_or("");
+                let token = match plain_name.parse::<PropertyKind>() {
+                    Ok(kind) if kind != PropertyKind::Extension => {
+                        Ok(Token::PropertyName)
+                    }
+                    _ => Ok(Token::ExtensionName),
+                };
+                self.parse_property(lex, token, card, name)
+            } else if let Some(group) = self.invalid_group(lex) {
+                Err(Error::InvalidGroup(group))
+            } else {
+                self.assert_token(
+                    Some(&first),
+                    &[
+                        Token::PropertyName,
+                        Token::ExtensionName,
+                        Token::TimeZone,
+                        Token::Geo,
+                    ],
+                )?;
+                let name = lex.slice();
+                self.parse_property(lex, first, card, name)
+            };
 
-            self.assert_token(
-                Some(&first),
-                &[
-                    Token::PropertyName,
-                    Token::ExtensionName,
-                    Token::TimeZone,
-                    Token::Geo,
-                ],
-            )?;
-
-            if let Err(e) = self.parse_property(lex, first, card) {
-                if self.strict {
-                    return Err(e);
+            if let Err(e) = result {
+                // Resume at the start of the next content line
+                if !matches!(lex.slice(), "\n" | "\r\n") {
+                    for token in lex.by_ref() {
+                        if token == Ok(Token::NewLine) {
+                            break;
+                        }
+                    }
                 }
+                if self.options.skip_invalid_properties {
+                    let (line, _) = self.position(start);
+                    self.skipped.push((line, e));
+                    continue;
+                }
+                if self.options.strict {
+                    let (line, column) = self.position(start);
+                    return Err(Error::At {
+                        line,
+                        column,
+                        source: Box::new(e),
+                    });
+                }
+                self.warnings.push(e.into());
             }
         }
         Ok(())
@@ -181,13 +372,13 @@ impl<'s> VcardParser<'s> {
 
     /// Parse a single property.
     fn parse_property(
-        &self,
+        &mut self,
         lex: &mut Lexer<'_, Token>,
         token: LexResult<Token>,
         card: &mut Vcard,
+        mut name: &str,
     ) -> Result<()> {
         let mut group: Option<String> = None;
-        let mut name = lex.slice();
 
         let period = name.find('.');
         if let Some(pos) = period {
@@ -201,13 +392,15 @@ impl<'s> VcardParser<'s> {
         if let Some(delimiter) = delimiter {
             if delimiter == Ok(Token::ParameterDelimiter) {
                 let parameters = self.parse_parameters(lex, name)?;
+                // Parameters that are dropped (CHARSET) may leave
+                // nothing behind
+                let parameters = if parameters == Parameters::default() {
+                    None
+                } else {
+                    Some(parameters)
+                };
                 self.parse_property_by_name(
-                    lex,
-                    token,
-                    card,
-                    name,
-                    Some(parameters),
-                    group,
+                    lex, token, card, name, parameters, group,
                 )?;
             } else if delimiter == Ok(Token::PropertyDelimiter) {
                 self.parse_property_by_name(
@@ -226,14 +419,19 @@ impl<'s> VcardParser<'s> {
     fn add_extension_parameter(
         &self,
         parameter_name: &str,
-        value: String,
+        values: Vec<String>,
         params: &mut Parameters,
     ) {
-        let values =
-            value.split(',').map(|s| s.to_owned()).collect::<Vec<_>>();
         let x_param = (parameter_name.to_owned(), values);
         if let Some(extensions) = params.extensions.as_mut() {
-            extensions.push(x_param);
+            // Keep the order used when writing so that parsed
+            // parameters compare equal after a round trip
+            let upper = parameter_name.to_ascii_uppercase();
+            let index = extensions
+                .iter()
+                .position(|(name, _)| name.to_ascii_uppercase() > upper)
+                .unwrap_or(extensions.len());
+            extensions.insert(index, x_param);
         } else {
             params.extensions = Some(vec![x_param]);
         }
@@ -241,7 +439,7 @@ impl<'s> VcardParser<'s> {
 
     /// Parse property parameters.
     fn parse_parameters(
-        &self,
+        &mut self,
         lex: &mut Lexer<'_, Token>,
         name: &str,
     ) -> Result<Parameters> {
@@ -250,28 +448,73 @@ impl<'s> VcardParser<'s> {
         let mut next: Option<LexResult<Token>> = lex.next();
 
         while let Some(token) = next.take() {
-            if token == Ok(Token::ParameterKey)
+            if let Some(end) = self.bare_parameter_end(lex) {
+                let values = lex.source()[lex.span().start..end]
+                    .split(',')
+                    .map(|value| value.to_owned())
+                    .collect::<Vec<_>>();
+                self.skip_to(lex, end)?;
+                let delimiter = lex.next();
+                self.add_type_parameters(
+                    &property_upper_name,
+                    &values,
+                    &mut params,
+                )?;
+                match delimiter {
+                    Some(Ok(Token::PropertyDelimiter)) => break,
+                    Some(Ok(Token::ParameterDelimiter)) => {
+                        next = lex.next();
+                        continue;
+                    }
+                    _ => return Err(Error::DelimiterExpected),
+                }
+            }
+
+            let known = token == Ok(Token::ParameterKey)
                 || token == Ok(Token::ExtensionName)
                 || token == Ok(Token::TimeZone)
-                || token == Ok(Token::Geo)
-            {
+                || token == Ok(Token::Geo);
+            let name_end = self
+                .name_end(lex, &['='], false)
+                .filter(|end| !known || lex.span().end != *end);
+
+            if known || name_end.is_some() {
                 let source = lex.source();
                 let span = lex.span();
-                let parameter_name = &source[span.start..span.end];
+                let end = name_end.unwrap_or(span.end);
+                let parameter_name = &source[span.start..end];
                 let upper_name = parameter_name.to_uppercase();
 
+                // Unknown IANA parameters are preserved as extensions
+                let is_extension = if name_end.is_some() {
+                    self.skip_to(lex, end)?;
+                    upper_name != TZ
+                        && upper_name != GEO
+                        && upper_name != CHARSET
+                } else {
+                    token == Ok(Token::ExtensionName)
+                };
+
                 self.assert_token(
                     lex.next().as_ref(),
                     &[Token::ValueDelimiter],
                 )?;
 
-                let (value, next_token, quoted) =
-                    self.parse_parameter_value(lex)?;
+                let (values, next_token, quoted) =
+                    self.parse_parameter_value(lex, &property_upper_name)?;
+                // Lists of IANA parameters are commonly quoted as a
+                // whole (eg: `TYPE="work,voice"`) so they are split again
+                let list = values
+                    .iter()
+                    .flat_map(|value| value.split(','))
+                    .map(|value| value.to_owned())
+                    .collect::<Vec<_>>();
+                let value = values.join(",");
 
-                if token == Ok(Token::ExtensionName) {
+                if is_extension {
                     self.add_extension_parameter(
                         parameter_name,
-                        value,
+                        values,
                         &mut params,
                     );
                 } else {
@@ -285,47 +528,25 @@ impl<'s> VcardParser<'s> {
                             params.value = Some(value);
                         }
                         PREF => {
-                            let value: u8 = value.parse()?;
-                            if !(1..=100).contains(&value) {
-                                return Err(Error::PrefOutOfRange(value));
-                            }
-                            params.pref = Some(value);
+                            let value: Pref = value.parse()?;
+                            params.pref = Some(value.get());
                         }
                         ALTID => {
                             params.alt_id = Some(value);
                         }
                         PID => {
                             let mut pids: Vec<Pid> = Vec::new();
-                            let values = value.split(',');
-                            for value in values {
+                            for value in list {
                                 pids.push(value.parse()?);
                             }
                             params.pid = Some(pids);
                         }
                         TYPE => {
-                            // Check this parameter is allowed
-                            if !TYPE_PROPERTIES
-                                .contains(&&property_upper_name[..])
-                                && !property_upper_name.starts_with("X-")
-                            {
-                                return Err(Error::TypeParameter(
-                                    property_upper_name,
-                                ));
-                            }
-
-                            let mut type_params: Vec<TypeParameter> =
-                                Vec::new();
-
-                            for val in value.split(',') {
-                                let param: TypeParameter = val.parse()?;
-                                type_params.push(param);
-                            }
-
-                            if let Some(types) = params.types.as_mut() {
-                                types.append(&mut type_params);
-                            } else {
-                                params.types = Some(type_params);
-                            }
+                            self.add_type_parameters(
+                                &property_upper_name,
+                                &list,
+                                &mut params,
+                            )?;
                         }
                         MEDIATYPE => {
                             parse_media_type(value, &mut params)?;
@@ -334,11 +555,14 @@ impl<'s> VcardParser<'s> {
                             params.calscale = Some(value);
                         }
                         SORT_AS => {
-                            let sort_values = value
-                                .split(',')
-                                .map(|s| s.to_string())
-                                .collect::<Vec<_>>();
-                            params.sort_as = Some(sort_values);
+                            if property_upper_name != N
+                                && property_upper_name != ORG
+                            {
+                                return Err(Error::InvalidSortAs(
+                                    property_upper_name,
+                                ));
+                            }
+                            params.sort_as = Some(list);
                         }
                         GEO => {
                             if !quoted {
@@ -380,10 +604,17 @@ impl<'s> VcardParser<'s> {
                             }
                             params.label = Some(value);
                         }
+                        CHARSET => {
+                            // Content is always UTF-8 so the
+                            // parameter is not retained
+                            if !value.eq_ignore_ascii_case("utf-8") {
+                                return Err(Error::CharsetParameter(value));
+                            }
+                        }
                         ENCODING => {
                             self.add_extension_parameter(
                                 parameter_name,
-                                value,
+                                values,
                                 &mut params,
                             );
                         }
@@ -409,87 +640,256 @@ impl<'s> VcardParser<'s> {
         Ok(params)
     }
 
-    /// Parse the raw value for a property parameter.
-    fn parse_parameter_value(
+    /// Find the end of a name that starts at the current token.
+    ///
+    /// Names are IANA tokens (optionally prefixed with a group) and
+    /// must be followed by one of the delimiters on the same line;
+    /// the returned offset is the position of the delimiter.
+    fn name_end(
         &self,
-        lex: &mut Lexer<'_, Token>,
-    ) -> Result<(String, LexResult<Token>, bool)> {
-        let mut first_range: Option<Range<usize>> = None;
-        let mut quoted = false;
-        let mut is_folded_or_escaped = false;
+        lex: &Lexer<'_, Token>,
+        delimiters: &[char],
+        allow_group: bool,
+    ) -> Option<usize> {
+        let start = lex.span().start;
+        let rest = &lex.source()[start..];
+        let pos = rest.find(|c: char| {
+            !(c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        })?;
+        let name = &rest[..pos];
+        let delimiter = rest[pos..].chars().next()?;
+        let parts = name.split('.').collect::<Vec<_>>();
+        let valid = delimiters.contains(&delimiter)
+            && parts.iter().all(|part| !part.is_empty())
+            && (parts.len() == 1 || (allow_group && parts.len() == 2));
+        if valid {
+            Some(start + pos)
+        } else {
+            None
+        }
+    }
 
-        while let Some(mut token) = lex.next() {
-            let span = lex.span();
+    /// Find a group name with illegal characters on the current line.
+    ///
+    /// The line must otherwise look like a content line so that
+    /// this error is preferred over a generic token error.
+    fn invalid_group(&self, lex: &Lexer<'_, Token>) -> Option<String> {
+        let rest = &lex.source()[lex.span().start..];
+        let end = rest.find([';', ':', '\r', '\n'])?;
+        let (group, name) = rest[..end].rsplit_once('.')?;
+        if valid_group(name) && !group.is_empty() && !valid_group(group) {
+            Some(group.to_string())
+        } else {
+            None
+        }
+    }
 
-            if token == Ok(Token::Control) {
-                return Err(Error::ControlCharacter(escape_control(
-                    lex.slice(),
-                )));
+    /// Advance the lexer until the current token ends at an offset.
+    fn skip_to(&self, lex: &mut Lexer<'_, Token>, end: usize) -> Result<()> {
+        while lex.span().end < end {
+            if lex.next().is_none() {
+                return Err(Error::TokenExpected);
             }
+        }
+        Ok(())
+    }
 
-            if token == Ok(Token::FoldedLine)
-                || token == Ok(Token::EscapedNewLine)
-                || token == Ok(Token::EscapedComma)
-                || token == Ok(Token::EscapedBackSlash)
-            {
-                is_folded_or_escaped = true;
-            }
+    /// Find the end of a vCard 3.0 parameter that has no name.
+    ///
+    /// Version 3.0 permits bare TYPE values such as
+    /// `TEL;HOME;VOICE:`; the returned offset is the position
+    /// of the delimiter following the value.
+    fn bare_parameter_end(&self, lex: &Lexer<'_, Token>) -> Option<usize> {
+        if self.version != Version::V3 {
+            return None;
+        }
+        let start = lex.span().start;
+        let pos = lex.source()[start..].find([';', ':', '='])?;
+        let delimiter = lex.source()[start + pos..].chars().next();
+        if pos == 0 || delimiter == Some('=') {
+            return None;
+        }
+        Some(start + pos)
+    }
 
-            let completed = if first_range.is_some() && quoted {
-                token == Ok(Token::DoubleQuote)
-            } else {
-                token == Ok(Token::PropertyDelimiter)
-                    || token == Ok(Token::ParameterDelimiter)
-                //|| token == Token::ParameterKey
-            };
+    /// Add TYPE parameter values for a property.
+    fn add_type_parameters(
+        &mut self,
+        property_upper_name: &str,
+        values: &[String],
+        params: &mut Parameters,
+    ) -> Result<()> {
+        // Check this parameter is allowed
+        if !allows_type_parameter(property_upper_name) {
+            return Err(Error::TypeParameter(
+                property_upper_name.to_string(),
+            ));
+        }
 
-            if first_range.is_none() {
-                first_range = Some(span.clone());
-                if token == Ok(Token::DoubleQuote) {
-                    quoted = true;
+        let mut type_params: Vec<TypeParameter> = Vec::new();
+        for val in values {
+            if self.version == Version::V3 {
+                // vCard 3.0 marks the preferred value with a type
+                if val.eq_ignore_ascii_case("pref") {
+                    params.pref.get_or_insert(1);
+                } else if let Some(param) =
+                    version3_type(property_upper_name, val)
+                {
+                    type_params.push(param);
                 }
+                continue;
             }
+            let param =
+                self.parse_type_parameter(property_upper_name, val)?;
+            type_params.push(param);
+        }
 
-            if completed {
-                let source = lex.source();
-                let begin = first_range.unwrap().start;
-                let end = span.start;
-                let mut value = &source[begin..end];
+        if type_params.is_empty() {
+            return Ok(());
+        }
+        if let Some(types) = params.types.as_mut() {
+            types.append(&mut type_params);
+        } else {
+            params.types = Some(type_params);
+        }
+        Ok(())
+    }
 
-                // Remove double quotes if necessary
-                if value.len() >= 2 && quoted {
-                    value = &source[begin + 1..end];
-                }
+    /// Parse a TYPE parameter value for a property.
+    ///
+    /// The TEL and RELATED properties define their own set of
+    /// types, unknown values are an error in strict mode otherwise
+    /// they are preserved as an unknown type.
+    fn parse_type_parameter(
+        &mut self,
+        property_upper_name: &str,
+        value: &str,
+    ) -> Result<TypeParameter> {
+        // Known type values are case-insensitive, others
+        // are kept as they were written
+        let raw = value;
+        let lower = value.to_lowercase();
+        let value = if !matches!(
+            lower.parse(),
+            Ok(TypeParameter::Extension(_) | TypeParameter::Unknown(_))
+        ) {
+            &lower[..]
+        } else {
+            value
+        };
+        let param: TypeParameter = value.parse()?;
+        let error = match (property_upper_name, &param) {
+            (TEL, TypeParameter::Related(_) | TypeParameter::Unknown(_)) => {
+                Error::UnknownTelephoneType(value.to_string())
+            }
+            (
+                RELATED,
+                TypeParameter::Telephone(_) | TypeParameter::Unknown(_),
+            ) => Error::UnknownRelatedType(value.to_string()),
+            (
+                ADR,
+                TypeParameter::Telephone(_)
+                | TypeParameter::Related(_)
+                | TypeParameter::Unknown(_),
+            ) => Error::UnknownAddressType(value.to_string()),
+            _ => return Ok(param),
+        };
 
-                // Must consumer the next token
-                if quoted {
-                    token = if let Some(Ok(token)) = lex.next() {
-                        if token != Token::PropertyDelimiter
-                            && token != Token::ParameterDelimiter
-                        {
-                            return Err(Error::DelimiterExpected);
-                        }
-                        Ok(token)
+        if self.options.strict {
+            Err(error)
+        } else {
+            self.warnings.push(error.into());
+            Ok(TypeParameter::Unknown(raw.to_string()))
+        }
+    }
+
+    /// Parse the raw value for a property parameter.
+    ///
+    /// The value is a list delimited by commas where each item
+    /// may be quoted; delimiters inside quotes are literal and
+    /// the quotes are removed. Items are decoded using the
+    /// circumflex escaping from RFC 6868.
+    fn parse_parameter_value(
+        &self,
+        lex: &mut Lexer<'_, Token>,
+        name: &str,
+    ) -> Result<(Vec<String>, LexResult<Token>, bool)> {
+        let source = lex.source();
+        let start = lex.span().end;
+        let mut values = Vec::new();
+        let mut item_start = start;
+        let mut in_quotes = false;
+        let mut quote_start = start;
+        let mut closed = false;
+        let mut is_escaped = false;
+        let mut delimiter = None;
+
+        for (index, c) in source[start..].char_indices() {
+            let pos = start + index;
+            if c == '\r' || c == '\n' {
+                break;
+            }
+            if c.is_control() && c != '\t' {
+                return Err(Error::ControlCharacter {
+                    value: escape_control(&c.to_string()),
+                    name: name.to_string(),
+                    offset: index,
+                });
+            }
+            if in_quotes {
+                if c == '"' {
+                    in_quotes = false;
+                    closed = true;
+                }
+                continue;
+            }
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+            match c {
+                '"' if pos == item_start => {
+                    in_quotes = true;
+                    quote_start = pos;
+                }
+                ',' | ';' | ':' => {
+                    values.push(parameter_item(&source[item_start..pos]));
+                    if c == ',' {
+                        item_start = pos + 1;
+                        closed = false;
                     } else {
-                        return Err(Error::TokenExpected);
-                    };
+                        delimiter = Some((pos, c));
+                        break;
+                    }
                 }
-
-                let value = if is_folded_or_escaped {
-                    unescape_value(value)
-                } else {
-                    value.to_string()
-                };
-
-                return Ok((value, token, quoted));
+                _ if closed => return Err(Error::DelimiterExpected),
+                '\\' => is_escaped = true,
+                _ => {}
             }
         }
-        Err(Error::TokenExpected)
+
+        if in_quotes {
+            return Err(
+                LexError::UnterminatedQuote(quote_start - start).into()
+            );
+        }
+        let (pos, c) = delimiter.ok_or(Error::TokenExpected)?;
+        self.skip_to(lex, pos + 1)?;
+        if lex.span().end != pos + 1 {
+            return Err(Error::DelimiterExpected);
+        }
+        let token = if c == ';' {
+            Ok(Token::ParameterDelimiter)
+        } else {
+            Ok(Token::PropertyDelimiter)
+        };
+        let quoted = source[start..].starts_with('"');
+        Ok((values, token, quoted))
     }
 
     /// Parse a property by name.
     fn parse_property_by_name(
-        &self,
+        &mut self,
         lex: &mut Lexer<'_, Token>,
         token: LexResult<Token>,
         card: &mut Vcard,
@@ -497,22 +897,40 @@ impl<'s> VcardParser<'s> {
         parameters: Option<Parameters>,
         group: Option<String>,
     ) -> Result<()> {
-        let value = self.parse_property_value(lex)?;
-
+        let raw_value = self.parse_property_value(lex, name)?;
         let upper_name = name.to_uppercase();
+        let extension =
+            token == Ok(Token::ExtensionName) || upper_name.starts_with("X-");
+
+        // Trailing whitespace is an artifact for values that are
+        // not free text (eg: URIs, dates and email addresses)
+        let raw_value =
+            if extension || TEXT_PROPERTIES.contains(&&*upper_name) {
+                raw_value
+            } else {
+                raw_value.trim_end_matches([' ', '\t'])
+            };
+        let value = unescape_text(raw_value);
 
-        if token == Ok(Token::ExtensionName) || upper_name.starts_with("X-") {
+        if extension {
             self.parse_extension_property_by_name(
-                card, name, value, parameters, group,
+                card, name, raw_value, value, parameters, group,
             )?;
+            card.order.push(PropertyKind::Extension);
             return Ok(());
         }
 
+        if let Ok(kind) = upper_name.parse::<PropertyKind>() {
+            if kind.is_singleton() && card.property(kind, 0).is_some() {
+                return Err(Error::OnlyOnce(upper_name));
+            }
+        }
+
         match &upper_name[..] {
             // General properties
             // https://www.rfc-editor.org/rfc/rfc6350#section-6.1
             SOURCE => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.source.push(UriProperty {
                     value,
                     parameters,
@@ -520,9 +938,6 @@ impl<'s> VcardParser<'s> {
                 });
             }
             KIND => {
-                if card.kind.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
                 let value: Kind = value.as_ref().parse()?;
                 card.kind = Some(KindProperty {
                     value,
@@ -547,31 +962,27 @@ impl<'s> VcardParser<'s> {
                 });
             }
             N => {
-                if card.name.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
-                let value = value
-                    .as_ref()
-                    .split(';')
-                    .map(|s| s.to_string())
-                    .collect::<Vec<_>>();
-                card.name = Some(TextListProperty {
+                let value: StructuredName = raw_value.parse()?;
+                card.name = Some(NameProperty {
                     value,
                     parameters,
                     group,
-                    delimiter: TextListDelimiter::SemiColon,
                 });
             }
             NICKNAME => {
-                card.nickname.push(TextProperty {
-                    value: value.into_owned(),
+                let value = split_unescaped(raw_value, ',')
+                    .into_iter()
+                    .map(|s| unescape_text(s).into_owned())
+                    .collect::<Vec<_>>();
+                card.nickname.push(TextListProperty {
+                    value,
                     parameters,
                     group,
+                    delimiter: TextListDelimiter::Comma,
                 });
             }
-            PHOTO => match Uri::try_from(value.as_ref()) {
-                Ok(uri) => {
-                    let value = uri.into_owned();
+            PHOTO => match UriValue::try_from(value.as_ref()) {
+                Ok(value) => {
                     card.photo.push(TextOrUriProperty::Uri(UriProperty {
                         value,
                         parameters,
@@ -587,10 +998,6 @@ impl<'s> VcardParser<'s> {
                 }
             },
             BDAY => {
-                if card.bday.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
-
                 let prop = parse_date_time_or_text(
                     &upper_name,
                     value,
@@ -600,10 +1007,6 @@ impl<'s> VcardParser<'s> {
                 card.bday = Some(prop);
             }
             ANNIVERSARY => {
-                if card.anniversary.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
-
                 let prop = parse_date_time_or_text(
                     &upper_name,
                     value,
@@ -613,10 +1016,7 @@ impl<'s> VcardParser<'s> {
                 card.anniversary = Some(prop);
             }
             GENDER => {
-                if card.gender.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
-                let value: Gender = value.as_ref().parse()?;
+                let value: Gender = raw_value.parse()?;
                 card.gender = Some(GenderProperty {
                     value,
                     parameters,
@@ -627,7 +1027,7 @@ impl<'s> VcardParser<'s> {
             // Delivery Addressing
             // https://www.rfc-editor.org/rfc/rfc6350#section-6.3
             ADR => {
-                let value: DeliveryAddress = value.as_ref().parse()?;
+                let value: DeliveryAddress = raw_value.parse()?;
                 card.address.push(AddressProperty {
                     value,
                     parameters,
@@ -653,7 +1053,7 @@ impl<'s> VcardParser<'s> {
                 });
             }
             IMPP => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.impp.push(UriProperty {
                     value,
                     parameters,
@@ -689,8 +1089,7 @@ impl<'s> VcardParser<'s> {
                                 .push(TimeZoneProperty::UtcOffset(value));
                         }
                         ValueType::Uri => {
-                            let value =
-                                Uri::try_from(value.as_ref())?.into_owned();
+                            let value = UriValue::try_from(value.as_ref())?;
                             card.timezone.push(TimeZoneProperty::Uri(
                                 UriProperty {
                                     value,
@@ -717,7 +1116,14 @@ impl<'s> VcardParser<'s> {
                 }
             }
             GEO => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                // vCard 3.0 uses a structured `lat;lon` value
+                let value = match raw_value.split_once(';') {
+                    Some((lat, lon)) if self.version == Version::V3 => {
+                        Cow::Owned(format!("geo:{},{}", lat, lon))
+                    }
+                    _ => value,
+                };
+                let value = UriValue::try_from(value.as_ref())?;
                 card.geo.push(UriProperty {
                     value,
                     parameters,
@@ -742,7 +1148,7 @@ impl<'s> VcardParser<'s> {
                 });
             }
             LOGO => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.logo.push(UriProperty {
                     value,
                     parameters,
@@ -750,20 +1156,15 @@ impl<'s> VcardParser<'s> {
                 });
             }
             ORG => {
-                let value = value
-                    .as_ref()
-                    .split(';')
-                    .map(|s| s.to_string())
-                    .collect::<Vec<_>>();
-                card.org.push(TextListProperty {
+                let value: Organization = raw_value.parse()?;
+                card.org.push(OrgProperty {
                     value,
                     parameters,
                     group,
-                    delimiter: TextListDelimiter::SemiColon,
                 });
             }
             MEMBER => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.member.push(UriProperty {
                     value,
                     parameters,
@@ -778,14 +1179,29 @@ impl<'s> VcardParser<'s> {
                 )?;
                 card.related.push(text_or_uri);
             }
+            // vCard 3.0 AGENT is translated to RELATED;TYPE=agent
+            AGENT if self.version == Version::V3 => {
+                let mut parameters = parameters.unwrap_or_default();
+                parameters
+                    .types
+                    .get_or_insert_with(Vec::new)
+                    .push(TypeParameter::Related(RelatedType::Agent));
+                let text_or_uri = self.parse_text_or_uri(
+                    value.as_ref(),
+                    Some(parameters),
+                    group,
+                )?;
+                card.related.push(text_or_uri);
+                card.order.push(PropertyKind::Related);
+                return Ok(());
+            }
 
             // Explanatory
             // https://www.rfc-editor.org/rfc/rfc6350#section-6.7
             CATEGORIES => {
-                let value = value
-                    .as_ref()
-                    .split(',')
-                    .map(|s| s.to_string())
+                let value = split_unescaped(raw_value, ',')
+                    .into_iter()
+                    .map(|s| unescape_text(s).into_owned())
                     .collect::<Vec<_>>();
                 card.categories.push(TextListProperty {
                     value,
@@ -802,9 +1218,6 @@ impl<'s> VcardParser<'s> {
                 });
             }
             PRODID => {
-                if card.prod_id.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
                 card.prod_id = Some(TextProperty {
                     value: value.into_owned(),
                     parameters,
@@ -812,9 +1225,6 @@ impl<'s> VcardParser<'s> {
                 });
             }
             REV => {
-                if card.rev.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
                 let value = parse_timestamp(value.as_ref())?;
                 card.rev = Some(DateTimeProperty {
                     value,
@@ -823,7 +1233,7 @@ impl<'s> VcardParser<'s> {
                 });
             }
             SOUND => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.sound.push(UriProperty {
                     value,
                     parameters,
@@ -831,9 +1241,6 @@ impl<'s> VcardParser<'s> {
                 });
             }
             UID => {
-                if card.uid.is_some() {
-                    return Err(Error::OnlyOnce(upper_name));
-                }
                 let text_or_uri = self.parse_text_or_uri(
                     value.as_ref(),
                     parameters,
@@ -856,7 +1263,7 @@ impl<'s> VcardParser<'s> {
                 });
             }
             URL => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.url.push(UriProperty {
                     value,
                     parameters,
@@ -881,7 +1288,7 @@ impl<'s> VcardParser<'s> {
             // Calendar
             // https://www.rfc-editor.org/rfc/rfc6350#section-6.9
             FBURL => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.fburl.push(UriProperty {
                     value,
                     parameters,
@@ -889,7 +1296,7 @@ impl<'s> VcardParser<'s> {
                 });
             }
             CALADRURI => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.cal_adr_uri.push(UriProperty {
                     value,
                     parameters,
@@ -897,7 +1304,7 @@ impl<'s> VcardParser<'s> {
                 });
             }
             CALURI => {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 card.cal_uri.push(UriProperty {
                     value,
                     parameters,
@@ -906,14 +1313,19 @@ impl<'s> VcardParser<'s> {
             }
             _ => return Err(Error::UnknownPropertyName(name.to_string())),
         }
+        card.order.push(upper_name.parse()?);
         Ok(())
     }
 
     /// Parse a private extension property (`x-`) by name.
+    ///
+    /// Lists of numbers are split on unescaped commas so
+    /// they are parsed from the raw value.
     fn parse_extension_property_by_name(
         &self,
         card: &mut Vcard,
         name: &str,
+        raw_value: &str,
         value: Cow<'_, str>,
         parameters: Option<Parameters>,
         group: Option<String>,
@@ -927,10 +1339,10 @@ impl<'s> VcardParser<'s> {
             match value_type {
                 ValueType::Text => AnyProperty::Text(value.into_owned()),
                 ValueType::Integer => {
-                    AnyProperty::Integer(parse_integer_list(value.as_ref())?)
+                    AnyProperty::Integer(parse_integer_list(raw_value)?)
                 }
                 ValueType::Float => {
-                    AnyProperty::Float(parse_float_list(value.as_ref())?)
+                    AnyProperty::Float(parse_float_list(raw_value)?)
                 }
                 ValueType::Boolean => {
                     AnyProperty::Boolean(parse_boolean(value.as_ref())?)
@@ -977,16 +1389,17 @@ impl<'s> VcardParser<'s> {
     }
 
     /// Get the slice for the property value.
+    ///
+    /// Escape sequences are retained so that structured values
+    /// can be split on unescaped delimiters.
     fn parse_property_value<'a>(
         &self,
         lex: &'a mut Lexer<'_, Token>,
-    ) -> Result<Cow<'a, str>> {
+        name: &str,
+    ) -> Result<&'a str> {
         let mut first_range: Option<Range<usize>> = None;
         let mut last_range: Option<Range<usize>> = None;
 
-        let mut needs_transform = false;
-        let mut tokens = Vec::new();
-
         while let Some(token) = lex.next() {
             let span = lex.span();
             if first_range.is_none() {
@@ -994,56 +1407,23 @@ impl<'s> VcardParser<'s> {
             }
 
             if token == Ok(Token::Control) {
-                return Err(Error::ControlCharacter(escape_control(
-                    lex.slice(),
-                )));
-            }
-
-            if token == Ok(Token::FoldedLine)
-                || token == Ok(Token::EscapedSemiColon)
-                || token == Ok(Token::EscapedComma)
-                || token == Ok(Token::EscapedNewLine)
-                || token == Ok(Token::EscapedBackSlash)
-            {
-                needs_transform = true;
+                let start = first_range.as_ref().map(|range| range.start);
+                return Err(Error::ControlCharacter {
+                    value: escape_control(lex.slice()),
+                    name: name.to_uppercase(),
+                    offset: span.start - start.unwrap_or(span.start),
+                });
             }
 
             if token == Ok(Token::NewLine) {
                 last_range = Some(span);
                 break;
             }
-
-            tokens.push((token, span));
         }
 
         if let (Some(first), Some(last)) = (first_range, last_range) {
-            if needs_transform {
-                let mut value = String::new();
-                for (token, span) in tokens {
-                    if token == Ok(Token::FoldedLine) {
-                        continue;
-                    } else if token == Ok(Token::EscapedComma) {
-                        value.push(',');
-                        continue;
-                    } else if token == Ok(Token::EscapedSemiColon) {
-                        value.push(';');
-                        continue;
-                    } else if token == Ok(Token::EscapedNewLine) {
-                        value.push('\n');
-                        continue;
-                    } else if token == Ok(Token::EscapedBackSlash) {
-                        value.push('\\');
-                        continue;
-                    }
-
-                    let source = lex.source();
-                    value.push_str(&source[span]);
-                }
-                Ok(Cow::Owned(value))
-            } else {
-                let source = lex.source();
-                Ok(Cow::Borrowed(&source[first.start..last.start]))
-            }
+            let source = lex.source();
+            Ok(&source[first.start..last.start])
         } else {
             Err(Error::InvalidPropertyValue)
         }
@@ -1069,7 +1449,7 @@ impl<'s> VcardParser<'s> {
                     group,
                 }))
             } else if let ValueType::Uri = value_type {
-                let value = Uri::try_from(value.as_ref())?.into_owned();
+                let value = UriValue::try_from(value.as_ref())?;
                 Ok(TextOrUriProperty::Uri(UriProperty {
                     value,
                     parameters,
@@ -1079,9 +1459,9 @@ impl<'s> VcardParser<'s> {
                 Err(Error::UnknownValueType(value_type.to_string()))
             }
         } else {
-            match Uri::try_from(value.as_ref()) {
+            match UriValue::try_from(value.as_ref()) {
                 Ok(value) => Ok(TextOrUriProperty::Uri(UriProperty {
-                    value: value.into_owned(),
+                    value,
                     parameters,
                     group,
                 })),
@@ -1112,6 +1492,117 @@ impl<'s> VcardParser<'s> {
     }
 }
 
+/// Unfold content lines.
+///
+/// A line break (CRLF, a bare LF or a bare CR) immediately followed
+/// by a single space or horizontal tab is removed so that a folded
+/// content line becomes one logical line before it is tokenized;
+/// any other bare CR is replaced with LF.
+pub(crate) fn unfold(source: &str) -> Cow<'_, str> {
+    unfold_with_folds(source).0
+}
+
+/// Determine if a group name is valid.
+///
+/// Group names may only contain letters, digits and hyphens.
+pub(crate) fn valid_group(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Length of the line break at the start of the bytes.
+///
+/// A lone carriage return or line feed is a line break
+/// as well as CRLF.
+pub(crate) fn line_break(bytes: &[u8]) -> usize {
+    match bytes {
+        [b'\r', b'\n', ..] => 2,
+        [b'\r' | b'\n', ..] => 1,
+        _ => 0,
+    }
+}
+
+/// Unfold content lines and return the offsets into the unfolded
+/// source where each folded line was joined.
+pub(crate) fn unfold_with_folds(source: &str) -> (Cow<'_, str>, Vec<usize>) {
+    let mut folds = Vec::new();
+    let bytes = source.as_bytes();
+    let mut value: Option<String> = None;
+    let mut start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        let line_break = line_break(&bytes[index..]);
+        if line_break > 0
+            && matches!(bytes.get(index + line_break), Some(b' ' | b'\t'))
+        {
+            let value = value
+                .get_or_insert_with(|| String::with_capacity(source.len()));
+            value.push_str(&source[start..index]);
+            folds.push(value.len());
+            index += line_break + 1;
+            start = index;
+        } else if line_break == 1 && bytes[index] == b'\r' {
+            // Same length so offsets are not affected
+            let value = value
+                .get_or_insert_with(|| String::with_capacity(source.len()));
+            value.push_str(&source[start..index]);
+            value.push('\n');
+            index += 1;
+            start = index;
+        } else {
+            index += line_break.max(1);
+        }
+    }
+
+    if let Some(mut value) = value {
+        value.push_str(&source[start..]);
+        (Cow::Owned(value), folds)
+    } else {
+        (Cow::Borrowed(source), folds)
+    }
+}
+
+/// Map a vCard 3.0 TYPE value to the 4.0 model.
+///
+/// Types that 4.0 defines for the property are converted and
+/// the INTERNET email type which 4.0 implies is dropped; any
+/// other value such as `IPHONE` or `X400` is kept verbatim.
+fn version3_type(
+    property_upper_name: &str,
+    value: &str,
+) -> Option<TypeParameter> {
+    let param = match (property_upper_name, &value.to_lowercase()[..]) {
+        (EMAIL, "internet") => return None,
+        (_, "home") => TypeParameter::Home,
+        (_, "work") => TypeParameter::Work,
+        (TEL, "voice") => TypeParameter::Telephone(TelephoneType::Voice),
+        (TEL, "fax") => TypeParameter::Telephone(TelephoneType::Fax),
+        (TEL, "cell") => TypeParameter::Telephone(TelephoneType::Cell),
+        (TEL, "video") => TypeParameter::Telephone(TelephoneType::Video),
+        (TEL, "pager") => TypeParameter::Telephone(TelephoneType::Pager),
+        (_, lower) if lower.starts_with("x-") => {
+            TypeParameter::Extension(value[2..].to_string())
+        }
+        _ => TypeParameter::Unknown(value.to_string()),
+    };
+    Some(param)
+}
+
+/// Unquote and decode a single parameter value.
+fn parameter_item(item: &str) -> String {
+    let item = if item.len() >= 2 && item.starts_with('"') {
+        &item[1..item.len() - 1]
+    } else {
+        item
+    };
+    let item = if item.contains('\\') {
+        Cow::Owned(unescape_value(item))
+    } else {
+        Cow::Borrowed(item)
+    };
+    decode_parameter(&item).into_owned()
+}
+
 fn parse_date_time_or_text(
     prop_name: &str,
     value: Cow<'_, str>,
@@ -16,17 +16,27 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "zeroize")]
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(feature = "mime")]
+use mime::Mime;
+
+use base64::{engine::general_purpose, Engine};
+
 use crate::{
+    builder::AddressBuilder,
     escape_value,
     helper::{
         format_date, format_date_and_or_time_list, format_date_list,
         format_date_time, format_date_time_list, format_float_list,
-        format_integer_list, format_time, format_time_list,
-        format_timestamp_list, format_utc_offset, parse_date,
-        parse_date_time, parse_time, parse_utc_offset,
+        format_integer_list, format_reduced_date_time, format_time,
+        format_time_list, format_timestamp, format_timestamp_list,
+        format_utc_offset, parse_date, parse_date_time, parse_time,
+        parse_utc_offset,
     },
-    parameter::Parameters,
-    Error, Result,
+    parameter::{
+        AddressType, CommonType, Parameters, Pref, TelephoneType,
+        TimeZoneParameter, TypeParameter, ValueType,
+    },
+    split_unescaped, unescape_text, Error, Result,
 };
 
 const INDIVIDUAL: &str = "individual";
@@ -41,6 +51,231 @@ pub trait Property: Display {
 
     /// Get the property parameters.
     fn parameters(&self) -> Option<&Parameters>;
+
+    /// Get the preference from the PREF parameter.
+    fn pref(&self) -> Option<Pref> {
+        self.parameters()
+            .and_then(|params| params.pref)
+            .and_then(|value| Pref::new(value).ok())
+    }
+
+    /// Get the type of the property value.
+    ///
+    /// Where a property may hold different kinds of value (eg: text
+    /// or a URI) this is determined by the parsed value so a VALUE
+    /// parameter that selects one of them is reflected. Otherwise
+    /// the type of the property is returned regardless of any VALUE
+    /// parameter, for example date and or time properties always
+    /// report `DateAndOrTime` and REV reports `Timestamp`.
+    fn value_type(&self) -> ValueType {
+        ValueType::Text
+    }
+
+    /// Get the values of the TYPE parameter.
+    fn common_types(&self) -> Vec<CommonType> {
+        self.parameters()
+            .and_then(|params| params.types.as_ref())
+            .map(|types| types.iter().map(CommonType::from).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Kinds of properties in a vCard.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PropertyKind {
+    /// The SOURCE property.
+    Source,
+    /// The KIND property.
+    Kind,
+    /// The XML property.
+    Xml,
+    /// The FN property.
+    FormattedName,
+    /// The N property.
+    Name,
+    /// The NICKNAME property.
+    Nickname,
+    /// The PHOTO property.
+    Photo,
+    /// The BDAY property.
+    Birthday,
+    /// The ANNIVERSARY property.
+    Anniversary,
+    /// The GENDER property.
+    Gender,
+    /// The URL property.
+    Url,
+    /// The ADR property.
+    Address,
+    /// The TITLE property.
+    Title,
+    /// The ROLE property.
+    Role,
+    /// The LOGO property.
+    Logo,
+    /// The ORG property.
+    Org,
+    /// The MEMBER property.
+    Member,
+    /// The RELATED property.
+    Related,
+    /// The TEL property.
+    Telephone,
+    /// The EMAIL property.
+    Email,
+    /// The IMPP property.
+    Impp,
+    /// The LANG property.
+    Lang,
+    /// The TZ property.
+    TimeZone,
+    /// The GEO property.
+    Geo,
+    /// The CATEGORIES property.
+    Categories,
+    /// The NOTE property.
+    Note,
+    /// The PRODID property.
+    ProdId,
+    /// The REV property.
+    Rev,
+    /// The SOUND property.
+    Sound,
+    /// The UID property.
+    Uid,
+    /// The CLIENTPIDMAP property.
+    ClientPidMap,
+    /// The KEY property.
+    Key,
+    /// The FBURL property.
+    FreeBusyUrl,
+    /// The CALADRURI property.
+    CalendarAddressUri,
+    /// The CALURI property.
+    CalendarUri,
+    /// Private property extension (`X-`) or unknown IANA property.
+    Extension,
+}
+
+impl PropertyKind {
+    /// All kinds of properties in the order they are written.
+    pub const ALL: [PropertyKind; 36] = [
+        Self::Source,
+        Self::Kind,
+        Self::Xml,
+        Self::FormattedName,
+        Self::Name,
+        Self::Nickname,
+        Self::Photo,
+        Self::Birthday,
+        Self::Anniversary,
+        Self::Gender,
+        Self::Url,
+        Self::Address,
+        Self::Title,
+        Self::Role,
+        Self::Logo,
+        Self::Org,
+        Self::Member,
+        Self::Related,
+        Self::Telephone,
+        Self::Email,
+        Self::Impp,
+        Self::Lang,
+        Self::TimeZone,
+        Self::Geo,
+        Self::Categories,
+        Self::Note,
+        Self::ProdId,
+        Self::Rev,
+        Self::Sound,
+        Self::Uid,
+        Self::ClientPidMap,
+        Self::Key,
+        Self::FreeBusyUrl,
+        Self::CalendarAddressUri,
+        Self::CalendarUri,
+        Self::Extension,
+    ];
+
+    /// Determine if the property may appear at most once.
+    pub fn is_singleton(&self) -> bool {
+        matches!(
+            self,
+            Self::Kind
+                | Self::Name
+                | Self::Birthday
+                | Self::Anniversary
+                | Self::Gender
+                | Self::ProdId
+                | Self::Rev
+                | Self::Uid
+        )
+    }
+
+    /// Get the property name.
+    ///
+    /// Extensions do not have a fixed name so `None` is returned.
+    pub fn name(&self) -> Option<&'static str> {
+        use crate::name::*;
+        Some(match self {
+            Self::Source => SOURCE,
+            Self::Kind => KIND,
+            Self::Xml => XML,
+            Self::FormattedName => FN,
+            Self::Name => N,
+            Self::Nickname => NICKNAME,
+            Self::Photo => PHOTO,
+            Self::Birthday => BDAY,
+            Self::Anniversary => ANNIVERSARY,
+            Self::Gender => GENDER,
+            Self::Url => URL,
+            Self::Address => ADR,
+            Self::Title => TITLE,
+            Self::Role => ROLE,
+            Self::Logo => LOGO,
+            Self::Org => ORG,
+            Self::Member => MEMBER,
+            Self::Related => RELATED,
+            Self::Telephone => TEL,
+            Self::Email => EMAIL,
+            Self::Impp => IMPP,
+            Self::Lang => LANG,
+            Self::TimeZone => TZ,
+            Self::Geo => GEO,
+            Self::Categories => CATEGORIES,
+            Self::Note => NOTE,
+            Self::ProdId => PRODID,
+            Self::Rev => REV,
+            Self::Sound => SOUND,
+            Self::Uid => UID,
+            Self::ClientPidMap => CLIENTPIDMAP,
+            Self::Key => KEY,
+            Self::FreeBusyUrl => FBURL,
+            Self::CalendarAddressUri => CALADRURI,
+            Self::CalendarUri => CALURI,
+            Self::Extension => return None,
+        })
+    }
+}
+
+impl FromStr for PropertyKind {
+    type Err = Error;
+
+    /// Parse from a property name, names beginning with `X-`
+    /// are extensions.
+    fn from_str(s: &str) -> Result<Self> {
+        let upper_name = s.to_uppercase();
+        if upper_name.starts_with("X-") {
+            return Ok(Self::Extension);
+        }
+        Self::ALL
+            .iter()
+            .find(|kind| kind.name() == Some(&upper_name[..]))
+            .copied()
+            .ok_or_else(|| Error::UnknownPropertyName(s.to_string()))
+    }
 }
 
 /// Delivery address for the ADR property.
@@ -64,6 +299,47 @@ pub struct DeliveryAddress {
     pub country_name: Option<String>,
 }
 
+impl DeliveryAddress {
+    /// Compose a multi-line mailing label.
+    ///
+    /// The post office box, extended address and street address
+    /// are written on separate lines followed by the locality,
+    /// region and postal code (eg: `Springfield, IL 62704`) and
+    /// the country name; empty components are skipped.
+    pub fn to_label(&self) -> String {
+        fn component(value: &Option<String>) -> Option<&str> {
+            value
+                .as_deref()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        }
+
+        let region = [component(&self.region), component(&self.postal_code)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let region = Some(&region[..]).filter(|value| !value.is_empty());
+        let city = [component(&self.locality), region]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        [
+            component(&self.po_box),
+            component(&self.extended_address),
+            component(&self.street_address),
+            Some(&city[..]).filter(|value| !value.is_empty()),
+            component(&self.country_name),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
 impl fmt::Display for DeliveryAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -104,74 +380,29 @@ impl fmt::Display for DeliveryAddress {
 impl FromStr for DeliveryAddress {
     type Err = Error;
 
+    /// Parse from the escaped text representation.
+    ///
+    /// The value must have exactly seven components.
     fn from_str(s: &str) -> Result<Self> {
-        let mut it = s.splitn(7, ';');
-        let po_box = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-        let extended_address = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-        let street_address = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-        let locality = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-        let region = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-        let postal_code = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-        let country_name = it
-            .next()
-            .ok_or_else(|| Error::InvalidAddress(s.to_string()))?;
-
-        let po_box = if !po_box.is_empty() {
-            Some(po_box.to_owned())
-        } else {
-            None
-        };
-        let extended_address = if !extended_address.is_empty() {
-            Some(extended_address.to_owned())
-        } else {
-            None
-        };
-        let street_address = if !street_address.is_empty() {
-            Some(street_address.to_owned())
-        } else {
-            None
-        };
-        let locality = if !locality.is_empty() {
-            Some(locality.to_owned())
-        } else {
-            None
-        };
-        let region = if !region.is_empty() {
-            Some(region.to_owned())
-        } else {
-            None
-        };
-        let postal_code = if !postal_code.is_empty() {
-            Some(postal_code.to_owned())
-        } else {
-            None
-        };
-        let country_name = if !country_name.is_empty() {
-            Some(country_name.to_owned())
-        } else {
-            None
-        };
-
+        let components = split_unescaped(s, ';');
+        if components.len() != 7 {
+            return Err(Error::InvalidAddress(s.to_string()));
+        }
+        let mut components = components.into_iter().map(|component| {
+            if component.is_empty() {
+                None
+            } else {
+                Some(unescape_text(component).into_owned())
+            }
+        });
         Ok(Self {
-            po_box,
-            extended_address,
-            street_address,
-            locality,
-            region,
-            postal_code,
-            country_name,
+            po_box: components.next().flatten(),
+            extended_address: components.next().flatten(),
+            street_address: components.next().flatten(),
+            locality: components.next().flatten(),
+            region: components.next().flatten(),
+            postal_code: components.next().flatten(),
+            country_name: components.next().flatten(),
         })
     }
 }
@@ -207,6 +438,261 @@ impl From<DeliveryAddress> for AddressProperty {
     }
 }
 
+impl AddressProperty {
+    /// Create a builder for an address property.
+    pub fn builder() -> AddressBuilder {
+        AddressBuilder::new()
+    }
+
+    /// Formatted mailing label from the LABEL parameter.
+    pub fn label(&self) -> Option<&str> {
+        self.parameters
+            .as_ref()
+            .and_then(|params| params.label.as_deref())
+    }
+
+    /// Latitude and longitude from the GEO parameter.
+    ///
+    /// Returns `None` when there is no GEO parameter or the
+    /// value is not a valid `geo:` URI.
+    pub fn geo(&self) -> Option<(f64, f64)> {
+        self.parameters
+            .as_ref()
+            .and_then(|params| params.geo.as_ref())
+            .and_then(|geo| geo.to_string().parse::<Coordinates>().ok())
+            .map(|coords| coords.lat_lon())
+    }
+
+    /// Address types from the TYPE parameter.
+    ///
+    /// Extension types are not included.
+    pub fn address_types(&self) -> Vec<AddressType> {
+        self.parameters
+            .as_ref()
+            .and_then(|params| params.types.as_ref())
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(|value| match value {
+                        TypeParameter::Home => Some(AddressType::Home),
+                        TypeParameter::Work => Some(AddressType::Work),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Time zone from the TZ parameter.
+    pub fn tz(&self) -> Option<&TimeZoneParameter> {
+        self.parameters
+            .as_ref()
+            .and_then(|params| params.timezone.as_ref())
+    }
+}
+
+/// Structured name for the N property.
+///
+/// Each component may contain multiple values.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct StructuredName {
+    /// The family names (surnames).
+    pub family_names: Vec<String>,
+    /// The given names.
+    pub given_names: Vec<String>,
+    /// The additional names.
+    pub additional_names: Vec<String>,
+    /// The honorific prefixes.
+    pub honorific_prefixes: Vec<String>,
+    /// The honorific suffixes.
+    pub honorific_suffixes: Vec<String>,
+}
+
+impl StructuredName {
+    /// Get the components in order.
+    pub fn components(&self) -> [&Vec<String>; 5] {
+        [
+            &self.family_names,
+            &self.given_names,
+            &self.additional_names,
+            &self.honorific_prefixes,
+            &self.honorific_suffixes,
+        ]
+    }
+}
+
+impl fmt::Display for StructuredName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, component) in self.components().iter().enumerate() {
+            if index > 0 {
+                write!(f, ";")?;
+            }
+            for (index, item) in component.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{}", escape_value(item, true))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for StructuredName {
+    type Err = Error;
+
+    /// Parse from the escaped text representation.
+    ///
+    /// Missing trailing components are treated as empty.
+    fn from_str(s: &str) -> Result<Self> {
+        let components = split_unescaped(s, ';');
+        if components.len() > 5 {
+            return Err(Error::InvalidName(s.to_string()));
+        }
+        let mut components = components.into_iter().map(|component| {
+            if component.is_empty() {
+                Vec::new()
+            } else {
+                split_unescaped(component, ',')
+                    .into_iter()
+                    .map(|value| unescape_text(value).into_owned())
+                    .collect()
+            }
+        });
+        Ok(Self {
+            family_names: components.next().unwrap_or_default(),
+            given_names: components.next().unwrap_or_default(),
+            additional_names: components.next().unwrap_or_default(),
+            honorific_prefixes: components.next().unwrap_or_default(),
+            honorific_suffixes: components.next().unwrap_or_default(),
+        })
+    }
+}
+
+/// Structured name property.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct NameProperty {
+    /// Group for this property.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub group: Option<String>,
+    /// The value for the property.
+    pub value: StructuredName,
+    /// The property parameters.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parameters: Option<Parameters>,
+}
+
+impl From<StructuredName> for NameProperty {
+    fn from(value: StructuredName) -> Self {
+        Self {
+            value,
+            group: None,
+            parameters: None,
+        }
+    }
+}
+
+/// Organization name and units for the ORG property.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct Organization {
+    /// The organization name.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
+    /// The organizational units.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub units: Vec<String>,
+}
+
+impl Organization {
+    /// Get the name followed by the units.
+    ///
+    /// A missing name is an empty string.
+    pub fn components(&self) -> Vec<&str> {
+        let mut components = vec![self.name.as_deref().unwrap_or_default()];
+        components.extend(self.units.iter().map(|unit| unit.as_str()));
+        components
+    }
+}
+
+impl fmt::Display for Organization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, item) in self.components().iter().enumerate() {
+            if index > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, "{}", escape_value(item, true))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Organization {
+    type Err = Error;
+
+    /// Parse from the escaped text representation.
+    ///
+    /// An empty organization name is treated as missing.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut components = split_unescaped(s, ';')
+            .into_iter()
+            .map(|component| unescape_text(component).into_owned());
+        let name = components.next().filter(|name| !name.is_empty());
+        Ok(Self {
+            name,
+            units: components.collect(),
+        })
+    }
+}
+
+/// Organization property.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct OrgProperty {
+    /// Group for this property.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub group: Option<String>,
+    /// The value for the property.
+    pub value: Organization,
+    /// The property parameters.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parameters: Option<Parameters>,
+}
+
+impl From<Organization> for OrgProperty {
+    fn from(value: Organization) -> Self {
+        Self {
+            value,
+            group: None,
+            parameters: None,
+        }
+    }
+}
+
 /// Value for the CLIENTPIDMAP property.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -216,9 +702,22 @@ pub struct ClientPidMap {
     pub source: u64,
     /// The URI for the map.
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::uri"))]
     pub uri: Uri<'static>,
 }
 
+impl ClientPidMap {
+    /// The source identifier.
+    pub fn source(&self) -> u64 {
+        self.source
+    }
+
+    /// The URI for the map.
+    pub fn uri(&self) -> &Uri<'static> {
+        &self.uri
+    }
+}
+
 impl fmt::Display for ClientPidMap {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{};{}", self.source, self.uri)
@@ -325,9 +824,11 @@ pub enum AnyProperty {
     Timestamp(Vec<OffsetDateTime>),
     /// URI property.
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::uri"))]
     Uri(Uri<'static>),
     /// UTC offset property.
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::utc_offset"))]
     UtcOffset(UtcOffset),
     /// Language property.
     #[cfg(feature = "language-tags")]
@@ -339,15 +840,37 @@ pub enum AnyProperty {
     Language(String),
 }
 
+impl AnyProperty {
+    /// Get the type of the value.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Self::Text(_) => ValueType::Text,
+            Self::Integer(_) => ValueType::Integer,
+            Self::Float(_) => ValueType::Float,
+            Self::Boolean(_) => ValueType::Boolean,
+            Self::Date(_) => ValueType::Date,
+            Self::DateTime(_) => ValueType::DateTime,
+            Self::Time(_) => ValueType::Time,
+            Self::DateAndOrTime(_) => ValueType::DateAndOrTime,
+            Self::Timestamp(_) => ValueType::Timestamp,
+            Self::Uri(_) => ValueType::Uri,
+            Self::UtcOffset(_) => ValueType::UtcOffset,
+            Self::Language(_) => ValueType::LanguageTag,
+        }
+    }
+}
+
 impl Eq for AnyProperty {}
 
 impl fmt::Display for AnyProperty {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Text(val) => write!(f, "{}", escape_value(val, false)),
+            Self::Text(val) => write!(f, "{}", escape_value(val, true)),
             Self::Integer(val) => format_integer_list(f, val),
             Self::Float(val) => format_float_list(f, val),
-            Self::Boolean(val) => write!(f, "{}", val),
+            Self::Boolean(val) => {
+                write!(f, "{}", if *val { "TRUE" } else { "FALSE" })
+            }
             Self::Date(val) => format_date_list(f, val),
             Self::DateTime(val) => format_date_time_list(f, val),
             Self::Time(val) => format_time_list(f, val),
@@ -447,19 +970,158 @@ impl fmt::Display for DateTimeProperty {
         write!(
             f,
             "{}",
-            format_date_time(&self.value).map_err(|_| fmt::Error)?
+            format_timestamp(&self.value).map_err(|_| fmt::Error)?
         )
     }
 }
 
+/// Date with reduced accuracy.
+///
+/// Represents the truncated forms of a date such as `--0415`
+/// (month and day), `1985` (year) or `---15` (day) without
+/// assigning values to the missing components.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartialDate {
+    year: Option<u16>,
+    month: Option<u8>,
+    day: Option<u8>,
+}
+
+impl PartialDate {
+    /// Create a new partial date.
+    ///
+    /// The components must form one of the reduced accuracy
+    /// forms: year, year and month, month, month and day or day.
+    pub fn new(
+        year: Option<u16>,
+        month: Option<u8>,
+        day: Option<u8>,
+    ) -> Result<Self> {
+        let date = Self { year, month, day };
+        let invalid = || Error::InvalidDate(format!("{:?}", date));
+        match (year, month, day) {
+            (Some(_), None, None)
+            | (Some(_), Some(_), None)
+            | (None, Some(_), _)
+            | (None, None, Some(_)) => {}
+            _ => return Err(invalid()),
+        }
+        if year.map(|year| year > 9999).unwrap_or(false)
+            || month
+                .map(|month| !(1..=12).contains(&month))
+                .unwrap_or(false)
+        {
+            return Err(invalid());
+        }
+        if let Some(day) = day {
+            // Use a leap year when the year is unknown
+            let max = match month {
+                Some(month) => time::Month::try_from(month)
+                    .map_err(|_| invalid())?
+                    .length(2000),
+                None => 31,
+            };
+            if !(1..=max).contains(&day) {
+                return Err(invalid());
+            }
+        }
+        Ok(date)
+    }
+
+    /// The year component.
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    /// The month component.
+    pub fn month(&self) -> Option<u8> {
+        self.month
+    }
+
+    /// The day component.
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+}
+
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.year, self.month, self.day) {
+            (Some(year), None, _) => write!(f, "{:04}", year),
+            (Some(year), Some(month), _) => {
+                write!(f, "{:04}-{:02}", year, month)
+            }
+            (None, Some(month), None) => write!(f, "--{:02}", month),
+            (None, Some(month), Some(day)) => {
+                write!(f, "--{:02}{:02}", month, day)
+            }
+            (None, None, Some(day)) => write!(f, "---{:02}", day),
+            (None, None, None) => Err(fmt::Error),
+        }
+    }
+}
+
+impl FromStr for PartialDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidDate(s.to_string());
+        let number = |value: &str, len: usize| {
+            if value.len() != len
+                || !value.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(invalid());
+            }
+            value.parse::<u16>().map_err(|_| invalid())
+        };
+        let two = |value: &str| number(value, 2).map(|value| value as u8);
+
+        let (year, month, day) = if let Some(day) = s.strip_prefix("---") {
+            (None, None, Some(two(day)?))
+        } else if let Some(rest) = s.strip_prefix("--") {
+            match rest.len() {
+                2 => (None, Some(two(rest)?), None),
+                4 if rest.is_ascii() => {
+                    (None, Some(two(&rest[..2])?), Some(two(&rest[2..])?))
+                }
+                _ => return Err(invalid()),
+            }
+        } else if s.len() == 4 {
+            (Some(number(s, 4)?), None, None)
+        } else if s.len() == 7 && s.as_bytes().get(4) == Some(&b'-') {
+            (Some(number(&s[..4], 4)?), Some(two(&s[5..])?), None)
+        } else {
+            return Err(invalid());
+        };
+
+        Self::new(year, month, day).map_err(|_| invalid())
+    }
+}
+
+/// Precision of a date and time with reduced accuracy.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimePrecision {
+    /// Only the hour is specified (eg: `20090808T14Z`).
+    Hour,
+    /// The hour and minute are specified (eg: `20090808T1430Z`).
+    Minute,
+}
+
 /// Date and or time.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DateAndOrTime {
     /// Date value.
     Date(Date),
+    /// Date with reduced accuracy.
+    PartialDate(PartialDate),
     /// Date and time value.
     DateTime(OffsetDateTime),
+    /// Date and time with reduced accuracy; the components of
+    /// the time after the precision are zero and not written.
+    ReducedDateTime(OffsetDateTime, TimePrecision),
     /// Time value.
     Time((Time, UtcOffset)),
 }
@@ -470,6 +1132,12 @@ impl From<Date> for DateAndOrTime {
     }
 }
 
+impl From<PartialDate> for DateAndOrTime {
+    fn from(value: PartialDate) -> Self {
+        Self::PartialDate(value)
+    }
+}
+
 impl From<OffsetDateTime> for DateAndOrTime {
     fn from(value: OffsetDateTime) -> Self {
         Self::DateTime(value)
@@ -488,11 +1156,18 @@ impl fmt::Display for DateAndOrTime {
             Self::Date(val) => {
                 write!(f, "{}", format_date(val).map_err(|_| fmt::Error)?)
             }
+            Self::PartialDate(val) => write!(f, "{}", val),
             Self::DateTime(val) => write!(
                 f,
                 "{}",
                 format_date_time(val).map_err(|_| fmt::Error)?
             ),
+            Self::ReducedDateTime(val, precision) => write!(
+                f,
+                "{}",
+                format_reduced_date_time(val, *precision)
+                    .map_err(|_| fmt::Error)?
+            ),
             Self::Time(val) => {
                 write!(f, "{}", format_time(val).map_err(|_| fmt::Error)?)
             }
@@ -504,12 +1179,31 @@ impl FromStr for DateAndOrTime {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        if !s.is_empty() && &s[0..1] == "T" {
-            return Ok(Self::Time(parse_time(&s[1..])?));
+        if let Some(time) = s.strip_prefix('T') {
+            return Ok(Self::Time(parse_time(time)?));
         }
 
+        let is_partial = !s.contains('T')
+            && (s.starts_with("--")
+                || s.len() == 4
+                || (s.len() == 7 && s.as_bytes().get(4) == Some(&b'-')));
+        if is_partial {
+            return Ok(Self::PartialDate(s.parse()?));
+        }
+
+        // Number of digits in the time of a date and time
+        let digits = s.split_once('T').map(|(_, time)| {
+            time.len()
+                - time.trim_start_matches(|c: char| c.is_ascii_digit()).len()
+        });
         match parse_date_time(s) {
-            Ok(value) => Ok(Self::DateTime(value)),
+            Ok(value) => Ok(match digits {
+                Some(2) => Self::ReducedDateTime(value, TimePrecision::Hour),
+                Some(4) => {
+                    Self::ReducedDateTime(value, TimePrecision::Minute)
+                }
+                _ => Self::DateTime(value),
+            }),
             Err(_) => match parse_date(s) {
                 Ok(value) => Ok(Self::Date(value)),
                 Err(_) => match parse_time(s) {
@@ -601,6 +1295,64 @@ impl From<Uri<'static>> for TextOrUriProperty {
     }
 }
 
+impl TextOrUriProperty {
+    /// Decode a `data:` URI value.
+    ///
+    /// Returns `None` for text values and URIs that are
+    /// not data URIs.
+    pub fn as_data_uri(&self) -> Result<Option<DataUri>> {
+        match self {
+            Self::Uri(val) => val.as_data_uri(),
+            Self::Text(_) => Ok(None),
+        }
+    }
+
+    /// Normalize a telephone number to the E.164 format.
+    ///
+    /// The value may be text or a `tel:` URI; numbers without a
+    /// country code are parsed for the default region (eg: `US`).
+    /// Returns `None` if the value is not a valid telephone number.
+    #[cfg(feature = "phonenumber")]
+    pub fn normalized_telephone(
+        &self,
+        default_region: &str,
+    ) -> Option<String> {
+        use phonenumber::{country::Id, Mode};
+        let value = match self {
+            Self::Text(val) => val.value.clone(),
+            Self::Uri(val) if val.scheme().eq_ignore_ascii_case("tel") => {
+                val.value.to_string()
+            }
+            Self::Uri(_) => return None,
+        };
+        let region = default_region.to_uppercase().parse::<Id>().ok();
+        let number = phonenumber::parse(region, value).ok()?;
+        if !phonenumber::is_valid(&number) {
+            return None;
+        }
+        Some(number.format().mode(Mode::E164).to_string())
+    }
+
+    /// Telephone types from the TYPE parameter.
+    ///
+    /// Only meaningful for the TEL property; other TYPE
+    /// values are ignored.
+    pub fn telephone_types(&self) -> Vec<TelephoneType> {
+        self.parameters()
+            .and_then(|params| params.types.as_ref())
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(|value| match value {
+                        TypeParameter::Telephone(tel) => Some(tel.clone()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
 impl Property for TextOrUriProperty {
     fn group(&self) -> Option<&String> {
         match self {
@@ -615,6 +1367,13 @@ impl Property for TextOrUriProperty {
             Self::Uri(val) => val.parameters(),
         }
     }
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Text(val) => val.value_type(),
+            Self::Uri(val) => val.value_type(),
+        }
+    }
 }
 
 impl fmt::Display for TextOrUriProperty {
@@ -676,6 +1435,13 @@ impl Property for DateTimeOrTextProperty {
             Self::DateTime(val) => val.parameters(),
         }
     }
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Text(val) => val.value_type(),
+            Self::DateTime(val) => val.value_type(),
+        }
+    }
 }
 
 impl fmt::Display for DateTimeOrTextProperty {
@@ -700,6 +1466,7 @@ pub struct UtcOffsetProperty {
     pub group: Option<String>,
     /// The value for the UTC offset.
     #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::utc_offset"))]
     pub value: UtcOffset,
     /// The parameters for the property.
     #[cfg_attr(
@@ -800,6 +1567,14 @@ impl Property for TimeZoneProperty {
             Self::UtcOffset(val) => val.parameters(),
         }
     }
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Text(val) => val.value_type(),
+            Self::Uri(val) => val.value_type(),
+            Self::UtcOffset(val) => val.value_type(),
+        }
+    }
 }
 
 impl fmt::Display for TimeZoneProperty {
@@ -835,7 +1610,7 @@ pub struct TextProperty {
 
 impl fmt::Display for TextProperty {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", escape_value(&self.value, false))
+        write!(f, "{}", escape_value(&self.value, true))
     }
 }
 
@@ -849,6 +1624,21 @@ impl From<String> for TextProperty {
     }
 }
 
+impl TextProperty {
+    /// Create a text property without a group or parameters.
+    ///
+    /// ```
+    /// use vcard4::property::TextProperty;
+    /// let prop = TextProperty::new("Jane Doe");
+    /// assert_eq!("Jane Doe", prop.value);
+    /// assert!(prop.group.is_none());
+    /// assert!(prop.parameters.is_none());
+    /// ```
+    pub fn new(value: impl Into<String>) -> Self {
+        value.into().into()
+    }
+}
+
 /// Delimiter used for a text list.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -909,9 +1699,7 @@ impl TextListProperty {
 impl fmt::Display for TextListProperty {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for (index, item) in self.value.iter().enumerate() {
-            let semi_colons =
-                matches!(self.delimiter, TextListDelimiter::SemiColon);
-            write!(f, "{}", escape_value(item, semi_colons))?;
+            write!(f, "{}", escape_value(item, true))?;
             if index < self.value.len() - 1 {
                 write!(
                     f,
@@ -927,6 +1715,88 @@ impl fmt::Display for TextListProperty {
     }
 }
 
+/// Text of a URI value.
+///
+/// The text is checked to be a valid URI when it is created
+/// and kept as it was given apart from the scheme which is
+/// converted to lower case; the URI components are
+/// parsed on demand so the text is the only copy of the value
+/// which means embedded payloads such as `data:` URIs are wiped
+/// on drop when the `zeroize` feature is enabled.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct UriValue(String);
+
+impl UriValue {
+    /// The URI text.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Parse the URI components.
+    pub fn uri(&self) -> Uri<'_> {
+        // Validated when the value was created
+        Uri::try_from(self.0.as_str()).expect("valid URI")
+    }
+
+    /// Scheme of the URI (eg: `xmpp` or `sip`).
+    pub fn scheme(&self) -> &str {
+        // A valid URI always starts with the scheme
+        self.0.split(':').next().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for UriValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for UriValue {
+    type Error = Error;
+
+    fn try_from(mut value: String) -> Result<Self> {
+        let scheme = Uri::try_from(value.as_str())?.scheme().as_str().len();
+        // Schemes are case-insensitive, use the canonical form
+        value[..scheme].make_ascii_lowercase();
+        Ok(Self(value))
+    }
+}
+
+impl TryFrom<&str> for UriValue {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.to_owned().try_into()
+    }
+}
+
+impl FromStr for UriValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.try_into()
+    }
+}
+
+impl PartialEq<Uri<'_>> for UriValue {
+    fn eq(&self, other: &Uri<'_>) -> bool {
+        &self.uri() == other
+    }
+}
+
+impl PartialEq<UriValue> for Uri<'_> {
+    fn eq(&self, other: &UriValue) -> bool {
+        other == self
+    }
+}
+
+impl From<Uri<'_>> for UriValue {
+    fn from(value: Uri<'_>) -> Self {
+        Self(value.to_string())
+    }
+}
+
 /// Uri property value.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -939,8 +1809,8 @@ pub struct UriProperty {
     )]
     pub group: Option<String>,
     /// Value for this property.
-    #[cfg_attr(feature = "zeroize", zeroize(skip))]
-    pub value: Uri<'static>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::uri_value"))]
+    pub value: UriValue,
     /// Parameters for this property.
     #[cfg_attr(
         feature = "serde",
@@ -949,8 +1819,8 @@ pub struct UriProperty {
     pub parameters: Option<Parameters>,
 }
 
-impl From<Uri<'static>> for UriProperty {
-    fn from(value: Uri<'static>) -> Self {
+impl From<UriValue> for UriProperty {
+    fn from(value: UriValue) -> Self {
         Self {
             value,
             group: None,
@@ -959,12 +1829,244 @@ impl From<Uri<'static>> for UriProperty {
     }
 }
 
+impl From<Uri<'static>> for UriProperty {
+    fn from(value: Uri<'static>) -> Self {
+        UriValue::from(value).into()
+    }
+}
+
 impl TryFrom<&str> for UriProperty {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self> {
-        let uri = Uri::try_from(value)?.into_owned();
-        Ok(uri.into())
+        Ok(UriValue::try_from(value)?.into())
+    }
+}
+
+impl UriProperty {
+    /// Parse a URI property without a group or parameters.
+    ///
+    /// ```
+    /// use vcard4::property::UriProperty;
+    /// let prop = UriProperty::parse("https://example.com/").unwrap();
+    /// assert_eq!("https", prop.scheme());
+    /// assert!(UriProperty::parse("not a uri").is_err());
+    /// ```
+    pub fn parse(value: &str) -> Result<Self> {
+        value.try_into()
+    }
+
+    /// Scheme of the URI value (eg: `xmpp` or `sip`).
+    ///
+    /// A parsed URI always has a scheme so this never fails.
+    pub fn scheme(&self) -> &str {
+        self.value.scheme()
+    }
+
+    /// Coordinates for a `geo:` URI value.
+    pub fn coordinates(&self) -> Result<Coordinates> {
+        self.value.as_str().parse()
+    }
+
+    /// Decode a `data:` URI value.
+    ///
+    /// Returns `None` when the value is not a data URI.
+    pub fn as_data_uri(&self) -> Result<Option<DataUri>> {
+        DataUri::decode(self.value.as_str())
+    }
+
+    /// Create a property with a base64 encoded `data:` URI value.
+    ///
+    /// The media type may be empty in which case the default
+    /// `text/plain` media type applies.
+    pub fn from_bytes(media_type: &str, data: &[u8]) -> Result<Self> {
+        let value = format!(
+            "data:{};base64,{}",
+            media_type,
+            general_purpose::STANDARD.encode(data)
+        );
+        value.as_str().try_into()
+    }
+}
+
+/// Media type and content decoded from a data URI.
+///
+/// See [RFC 2397](https://www.rfc-editor.org/rfc/rfc2397).
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct DataUri {
+    /// The media type.
+    #[cfg(feature = "mime")]
+    #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    pub media_type: Option<Mime>,
+    /// The media type.
+    #[cfg(not(feature = "mime"))]
+    pub media_type: Option<String>,
+    /// The decoded content.
+    pub data: Vec<u8>,
+}
+
+impl DataUri {
+    /// Decode a data URI.
+    fn decode(value: &str) -> Result<Option<Self>> {
+        let rest = match value.get(..5) {
+            Some(scheme) if scheme.eq_ignore_ascii_case("data:") => {
+                &value[5..]
+            }
+            _ => return Ok(None),
+        };
+        let (meta, content) = match rest.split_once(',') {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+
+        let (meta, is_base64) = match meta.rsplit_once(';') {
+            Some((meta, encoding))
+                if encoding.eq_ignore_ascii_case("base64") =>
+            {
+                (meta, true)
+            }
+            _ => (meta, false),
+        };
+
+        let data = if is_base64 {
+            general_purpose::STANDARD.decode(content)?
+        } else {
+            percent_decode(content)
+        };
+
+        let media_type = if meta.is_empty() {
+            None
+        } else {
+            #[cfg(feature = "mime")]
+            {
+                Some(meta.parse()?)
+            }
+            #[cfg(not(feature = "mime"))]
+            {
+                Some(meta.to_string())
+            }
+        };
+
+        Ok(Some(Self { media_type, data }))
+    }
+}
+
+/// Decode percent-encoded octets.
+fn percent_decode(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut data = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let decoded = if bytes[index] == b'%' {
+            value
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+        if let Some(byte) = decoded {
+            data.push(byte);
+            index += 3;
+        } else {
+            data.push(bytes[index]);
+            index += 1;
+        }
+    }
+    data
+}
+
+/// Coordinates parsed from a geo URI.
+///
+/// See [RFC 5870](https://www.rfc-editor.org/rfc/rfc5870).
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coordinates {
+    /// Latitude in decimal degrees.
+    pub latitude: f64,
+    /// Longitude in decimal degrees.
+    pub longitude: f64,
+    /// Altitude in meters.
+    pub altitude: Option<f64>,
+    /// Uncertainty in meters.
+    pub uncertainty: Option<f64>,
+}
+
+impl Coordinates {
+    /// Latitude and longitude pair.
+    pub fn lat_lon(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "geo:{},{}", self.latitude, self.longitude)?;
+        if let Some(altitude) = self.altitude {
+            write!(f, ",{}", altitude)?;
+        }
+        if let Some(uncertainty) = self.uncertainty {
+            write!(f, ";u={}", uncertainty)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Coordinates {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidGeoUri(s.to_string());
+        let path = s
+            .get(..4)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("geo:"))
+            .map(|_| &s[4..])
+            .ok_or_else(invalid)?;
+
+        let mut parts = path.split(';');
+        let coords = parts
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(|value| value.parse::<f64>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let (latitude, longitude, altitude) = match coords[..] {
+            [latitude, longitude] => (latitude, longitude, None),
+            [latitude, longitude, altitude] => {
+                (latitude, longitude, Some(altitude))
+            }
+            _ => return Err(invalid()),
+        };
+
+        if !(-90.0..=90.0).contains(&latitude)
+            || !(-180.0..=180.0).contains(&longitude)
+        {
+            return Err(invalid());
+        }
+
+        let mut uncertainty = None;
+        for param in parts {
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+            if name.eq_ignore_ascii_case("u") {
+                let value = value.parse::<f64>().map_err(|_| invalid())?;
+                if value < 0.0 {
+                    return Err(invalid());
+                }
+                uncertainty = Some(value);
+            } else if name.eq_ignore_ascii_case("crs")
+                && !value.eq_ignore_ascii_case("wgs84")
+            {
+                return Err(invalid());
+            }
+        }
+
+        Ok(Self {
+            latitude,
+            longitude,
+            altitude,
+            uncertainty,
+        })
     }
 }
 
@@ -1000,7 +2102,7 @@ impl From<Kind> for KindProperty {
 }
 
 /// Kind of vCard.
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
@@ -1013,8 +2115,10 @@ pub enum Kind {
     Org,
     /// A location.
     Location,
-    // TODO: x-name
-    // TODO: iana-token
+    /// Kind registered with IANA (eg: `device` or `application`).
+    Iana(String),
+    /// Private kind using the `x-` prefix, the prefix is retained.
+    Extension(String),
 }
 
 impl fmt::Display for Kind {
@@ -1027,6 +2131,7 @@ impl fmt::Display for Kind {
                 Self::Group => GROUP,
                 Self::Org => ORG,
                 Self::Location => LOCATION,
+                Self::Iana(value) | Self::Extension(value) => value,
             }
         )
     }
@@ -1041,7 +2146,18 @@ impl FromStr for Kind {
             GROUP => Ok(Self::Group),
             ORG => Ok(Self::Org),
             LOCATION => Ok(Self::Location),
-            _ => Err(Error::UnknownKind(s.to_string())),
+            _ => {
+                let is_token = !s.is_empty()
+                    && s.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-');
+                if !is_token {
+                    Err(Error::UnknownKind(s.to_string()))
+                } else if s.len() > 2 && s[0..2].eq_ignore_ascii_case("x-") {
+                    Ok(Self::Extension(s.to_string()))
+                } else {
+                    Ok(Self::Iana(s.to_string()))
+                }
+            }
         }
     }
 }
@@ -1078,7 +2194,9 @@ impl From<Gender> for GenderProperty {
 }
 
 /// Represents a gender associated with a vCard.
-#[derive(Debug, Eq, PartialEq, Clone)]
+///
+/// The default has no sex and no identity.
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct Gender {
@@ -1101,12 +2219,13 @@ impl fmt::Display for Gender {
 impl FromStr for Gender {
     type Err = Error;
 
+    /// Parse from the escaped text representation.
+    ///
+    /// The sex is separated from the identity by the first
+    /// unescaped semi-colon, the identity is unescaped.
     fn from_str(s: &str) -> Result<Self> {
         if s.is_empty() {
-            return Ok(Gender {
-                sex: Sex::None,
-                identity: None,
-            });
+            return Ok(Default::default());
         }
 
         let mut it = s.splitn(2, ';');
@@ -1117,7 +2236,7 @@ impl FromStr for Gender {
             identity: None,
         };
         if let Some(identity) = it.next() {
-            gender.identity = Some(identity.to_string());
+            gender.identity = Some(unescape_text(identity).into_owned());
         }
 
         Ok(gender)
@@ -1125,11 +2244,14 @@ impl FromStr for Gender {
 }
 
 /// Enumeration for sex.
-#[derive(Debug, Eq, PartialEq, Clone)]
+///
+/// Values are ordered as they are declared.
+#[derive(Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub enum Sex {
     /// No sex specified.
+    #[default]
     None,
     /// Male sex.
     Male,
@@ -1176,8 +2298,120 @@ impl FromStr for Sex {
     }
 }
 
+/// Owned property of any type.
+///
+/// Allows a heterogeneous collection of properties to be
+/// stored and, with the `serde` feature, serialized with
+/// a tag identifying the type of each property so that it
+/// can be reconstructed.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "property", rename_all = "kebab-case")
+)]
+pub enum TaggedProperty {
+    /// Delivery address property.
+    Address(AddressProperty),
+    /// Client PID map property.
+    ClientPidMap(ClientPidMapProperty),
+    /// Date and or time property.
+    DateAndOrTime(DateAndOrTimeProperty),
+    /// Timestamp property.
+    DateTime(DateTimeProperty),
+    /// Date and or time or text property.
+    DateTimeOrText(DateTimeOrTextProperty),
+    /// Extension property.
+    Extension(ExtensionProperty),
+    /// Gender property.
+    Gender(GenderProperty),
+    /// Kind property.
+    Kind(KindProperty),
+    /// Language property.
+    Language(LanguageProperty),
+    /// Structured name property.
+    Name(NameProperty),
+    /// Organization property.
+    Org(OrgProperty),
+    /// Text property.
+    Text(TextProperty),
+    /// Text list property.
+    TextList(TextListProperty),
+    /// Text or URI property.
+    TextOrUri(TextOrUriProperty),
+    /// Time zone property.
+    TimeZone(TimeZoneProperty),
+    /// URI property.
+    Uri(UriProperty),
+    /// UTC offset property.
+    UtcOffset(UtcOffsetProperty),
+}
+
+macro_rules! tagged_property {
+    ($($variant:ident($prop:ty)),* $(,)?) => {
+        $(
+            impl From<$prop> for TaggedProperty {
+                fn from(value: $prop) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )*
+
+        impl Property for TaggedProperty {
+            fn group(&self) -> Option<&String> {
+                match self {
+                    $(Self::$variant(val) => val.group(),)*
+                }
+            }
+
+            fn parameters(&self) -> Option<&Parameters> {
+                match self {
+                    $(Self::$variant(val) => val.parameters(),)*
+                }
+            }
+
+            fn value_type(&self) -> ValueType {
+                match self {
+                    $(Self::$variant(val) => val.value_type(),)*
+                }
+            }
+        }
+
+        impl fmt::Display for TaggedProperty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(Self::$variant(val) => write!(f, "{}", val),)*
+                }
+            }
+        }
+    };
+}
+
+tagged_property!(
+    Address(AddressProperty),
+    ClientPidMap(ClientPidMapProperty),
+    DateAndOrTime(DateAndOrTimeProperty),
+    DateTime(DateTimeProperty),
+    DateTimeOrText(DateTimeOrTextProperty),
+    Extension(ExtensionProperty),
+    Gender(GenderProperty),
+    Kind(KindProperty),
+    Language(LanguageProperty),
+    Name(NameProperty),
+    Org(OrgProperty),
+    Text(TextProperty),
+    TextList(TextListProperty),
+    TextOrUri(TextOrUriProperty),
+    TimeZone(TimeZoneProperty),
+    Uri(UriProperty),
+    UtcOffset(UtcOffsetProperty),
+);
+
 macro_rules! property_impl {
     ($prop:ty) => {
+        property_impl!($prop, ValueType::Text);
+    };
+    ($prop:ty, $value_type:expr) => {
         impl Property for $prop {
             fn group(&self) -> Option<&String> {
                 self.group.as_ref()
@@ -1186,6 +2420,10 @@ macro_rules! property_impl {
             fn parameters(&self) -> Option<&Parameters> {
                 self.parameters.as_ref()
             }
+
+            fn value_type(&self) -> ValueType {
+                $value_type
+            }
         }
     };
 }
@@ -1203,7 +2441,13 @@ macro_rules! display_impl {
 property_impl!(AddressProperty);
 display_impl!(AddressProperty);
 
-property_impl!(UriProperty);
+property_impl!(NameProperty);
+display_impl!(NameProperty);
+
+property_impl!(OrgProperty);
+display_impl!(OrgProperty);
+
+property_impl!(UriProperty, ValueType::Uri);
 display_impl!(UriProperty);
 
 property_impl!(KindProperty);
@@ -1211,12 +2455,12 @@ display_impl!(KindProperty);
 
 property_impl!(TextProperty);
 
-property_impl!(LanguageProperty);
+property_impl!(LanguageProperty, ValueType::LanguageTag);
 display_impl!(LanguageProperty);
 
-property_impl!(DateTimeProperty);
+property_impl!(DateTimeProperty, ValueType::Timestamp);
 
-property_impl!(DateAndOrTimeProperty);
+property_impl!(DateAndOrTimeProperty, ValueType::DateAndOrTime);
 
 property_impl!(ClientPidMapProperty);
 display_impl!(ClientPidMapProperty);
@@ -1224,12 +2468,24 @@ display_impl!(ClientPidMapProperty);
 property_impl!(GenderProperty);
 display_impl!(GenderProperty);
 
-property_impl!(ExtensionProperty);
+impl Property for ExtensionProperty {
+    fn group(&self) -> Option<&String> {
+        self.group.as_ref()
+    }
+
+    fn parameters(&self) -> Option<&Parameters> {
+        self.parameters.as_ref()
+    }
+
+    fn value_type(&self) -> ValueType {
+        self.value.value_type()
+    }
+}
 display_impl!(ExtensionProperty);
 
 // Bespoke Display implementations
 property_impl!(TextListProperty);
-property_impl!(UtcOffsetProperty);
+property_impl!(UtcOffsetProperty, ValueType::UtcOffset);
 
 #[cfg(test)]
 mod tests {
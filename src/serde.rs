@@ -63,6 +63,153 @@ pub(crate) mod media_type {
     }
 }
 
+pub(crate) mod uri {
+    use serde::{
+        de::{Deserialize, Deserializer, Error},
+        ser::Serializer,
+    };
+    use uriparse::uri::URI as Uri;
+
+    pub fn serialize<S>(
+        source: &Uri<'static>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(source)
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Uri<'static>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let uri = Uri::try_from(&value[..]).map_err(Error::custom)?;
+        Ok(uri.into_owned())
+    }
+}
+
+pub(crate) mod uri_value {
+    use serde::{
+        de::{Deserialize, Deserializer, Error},
+        ser::Serializer,
+    };
+
+    use crate::property::UriValue;
+
+    pub fn serialize<S>(
+        source: &UriValue,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(source.as_str())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<UriValue, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.try_into().map_err(Error::custom)
+    }
+}
+
+pub(crate) mod uri_option {
+    use serde::{
+        de::{Deserializer, Visitor},
+        ser::Serializer,
+    };
+    use std::fmt;
+    use uriparse::uri::URI as Uri;
+
+    pub fn serialize<S>(
+        source: &Option<Uri<'static>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if let Some(uri) = source {
+            super::uri::serialize(uri, serializer)
+        } else {
+            serializer.serialize_none()
+        }
+    }
+
+    struct UriVisitor;
+
+    impl<'de> Visitor<'de> for UriVisitor {
+        type Value = Option<Uri<'static>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a valid URI (eg: geo:37.386013,-122.082932)")
+        }
+
+        fn visit_some<D>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Some(super::uri::deserialize(deserializer)?))
+        }
+
+        // NOTE: we don't need to implement visit_none()
+        // NOTE: as we use skip_serializing_if on these properties
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<Uri<'static>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(UriVisitor)
+    }
+}
+
+pub(crate) mod utc_offset {
+    use crate::helper::{format_utc_offset, parse_utc_offset};
+    use serde::{
+        de::{Deserialize, Deserializer, Error},
+        ser::Serializer,
+    };
+    use std::fmt;
+    use time::UtcOffset;
+
+    struct Offset<'a>(&'a UtcOffset);
+
+    impl fmt::Display for Offset<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            format_utc_offset(f, self.0)
+        }
+    }
+
+    pub fn serialize<S>(
+        source: &UtcOffset,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&Offset(source))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<UtcOffset, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        parse_utc_offset(&value).map_err(Error::custom)
+    }
+}
+
 #[cfg(all(feature = "serde", feature = "mime"))]
 #[test]
 fn serde_media_type() -> anyhow::Result<()> {
@@ -79,3 +226,74 @@ fn serde_media_type() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn serde_uri() -> anyhow::Result<()> {
+    use crate::property::UriProperty;
+    use uriparse::uri::URI as Uri;
+
+    let prop: UriProperty =
+        Uri::try_from("https://example.com/")?.into_owned().into();
+    let encoded = serde_json::to_string(&prop)?;
+    assert_eq!(r#"{"value":"https://example.com/"}"#, encoded);
+    let decoded: UriProperty = serde_json::from_str(&encoded)?;
+    assert_eq!(prop, decoded);
+
+    assert!(
+        serde_json::from_str::<UriProperty>(r#"{"value":"a b"}"#).is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn serde_utc_offset() -> anyhow::Result<()> {
+    use crate::property::UtcOffsetProperty;
+
+    let prop: UtcOffsetProperty = "+0530".parse()?;
+    let encoded = serde_json::to_string(&prop)?;
+    assert_eq!(r#"{"value":"+0530"}"#, encoded);
+    let decoded: UtcOffsetProperty = serde_json::from_str(&encoded)?;
+    assert_eq!(prop, decoded);
+
+    assert!(
+        serde_json::from_str::<UtcOffsetProperty>(r#"{"value":"0530"}"#)
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn serde_tagged_property() -> anyhow::Result<()> {
+    use crate::property::{
+        Property, TaggedProperty, TextProperty, UriProperty,
+    };
+    use uriparse::uri::URI as Uri;
+
+    let text: TextProperty = String::from("Jane Doe").into();
+    let uri: UriProperty =
+        Uri::try_from("https://example.com/")?.into_owned().into();
+    let props: Vec<TaggedProperty> = vec![text.into(), uri.into()];
+
+    let encoded = serde_json::to_string(&props)?;
+    assert_eq!(
+        r#"[{"type":"text","property":{"value":"Jane Doe"}},{"type":"uri","property":{"value":"https://example.com/"}}]"#,
+        encoded
+    );
+
+    let decoded: Vec<TaggedProperty> = serde_json::from_str(&encoded)?;
+    assert_eq!(props, decoded);
+    assert!(matches!(decoded[0], TaggedProperty::Text(_)));
+    assert!(matches!(decoded[1], TaggedProperty::Uri(_)));
+    assert_eq!("Jane Doe", decoded[0].to_string());
+    assert!(decoded[1].parameters().is_none());
+
+    let boxed: Vec<Box<dyn Property>> = decoded
+        .into_iter()
+        .map(|prop| Box::new(prop) as _)
+        .collect();
+    assert_eq!("https://example.com/", boxed[1].to_string());
+
+    Ok(())
+}
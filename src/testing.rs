@@ -0,0 +1,64 @@
+//! Helpers for testing vCard fixtures.
+use crate::{parse, Vcard};
+
+/// Assert that vCard text survives a round trip.
+///
+/// Every vCard in the input is parsed, serialized and parsed
+/// again and the result must be structurally equal to the first
+/// parse.
+///
+/// # Panics
+///
+/// If the input or the serialized text cannot be parsed or a
+/// vCard changes during the round trip.
+pub fn assert_roundtrip(input: &str) {
+    let cards = match parse(input) {
+        Ok(cards) => cards,
+        Err(e) => panic!("failed to parse input: {}", e),
+    };
+    for card in cards {
+        let encoded = card.to_string();
+        let decoded: Vcard = match encoded.parse() {
+            Ok(decoded) => decoded,
+            Err(e) => panic!("failed to parse encoded vCard: {}", e),
+        };
+        assert_eq!(card, decoded, "vCard changed after a round trip");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_roundtrip;
+
+    #[test]
+    fn roundtrip() {
+        assert_roundtrip(
+            r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+N:Doe;Jane;;Dr.;
+NICKNAME:Janey,JD
+GENDER:F
+BDAY:19850412
+ADR;TYPE=home:;;123 Main Street;Any Town;CA;91921;U.S.A.
+TEL;VALUE=uri;TYPE=cell:tel:+1-555-555-5555
+EMAIL;PREF=1:jane@example.com
+item1.X-ABLabel:Work
+ORG:ABC\, Inc.;Marketing
+NOTE:Line one\nLine two
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+KIND:group
+FN:The Doe family
+MEMBER:urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af
+END:VCARD"#,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse input")]
+    fn roundtrip_invalid() {
+        assert_roundtrip("BEGIN:VCARD\r\nEND:VCARD");
+    }
+}
@@ -0,0 +1,175 @@
+//! Token stream for vCard content lines.
+use crate::{
+    error::LexError,
+    name::BOM,
+    parser::{line_break, valid_group},
+    Error, Result,
+};
+use std::ops::Range;
+
+/// Token in the content lines of a vCard.
+///
+/// Each token holds the byte range in the input; folded lines
+/// are not unfolded so a range may span a fold.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Token {
+    /// Group name before a property name.
+    Group(Range<usize>),
+    /// Period between a group and property name.
+    GroupSeparator(Range<usize>),
+    /// Name of a property.
+    PropertyName(Range<usize>),
+    /// Name of a parameter.
+    ParamName(Range<usize>),
+    /// Value of a parameter including any quotes.
+    ParamValue(Range<usize>),
+    /// Colon between the name and parameters and the value.
+    ValueSeparator(Range<usize>),
+    /// Value of a property.
+    Value(Range<usize>),
+    /// Line break at the end of a content line.
+    Newline(Range<usize>),
+}
+
+/// Split vCard text into tokens.
+///
+/// Only the structure of each content line is recognized,
+/// property and parameter values are not validated. Control
+/// characters other than horizontal tab are an error.
+///
+/// Line breaks, groups and byte order marks follow the same
+/// rules as the parser: CRLF, CR and LF all end a content line,
+/// a group must be a valid group name and a byte order mark at
+/// the start of a content line is skipped.
+pub fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut scanner = Scanner {
+        bytes: input.as_bytes(),
+        position: 0,
+    };
+
+    while !scanner.at_end() {
+        let start = scanner.position;
+        if let Some(len) = scanner.line_break() {
+            scanner.position += len;
+            tokens.push(Token::Newline(start..scanner.position));
+            continue;
+        }
+        if input[start..].starts_with(BOM) {
+            scanner.position += BOM.len_utf8();
+            continue;
+        }
+
+        let (end, delimiter) = scanner.scan(b";:", false)?;
+        let name = &input[start..end];
+        if let Some(pos) = name.find('.') {
+            if !valid_group(&name[..pos]) {
+                return Err(Error::InvalidGroup(name[..pos].to_string()));
+            }
+            tokens.push(Token::Group(start..start + pos));
+            tokens.push(Token::GroupSeparator(start + pos..start + pos + 1));
+            tokens.push(Token::PropertyName(start + pos + 1..end));
+        } else {
+            tokens.push(Token::PropertyName(start..end));
+        }
+
+        let mut delimiter = delimiter;
+        while delimiter == Some(b';') {
+            scanner.position += 1;
+            let start = scanner.position;
+            let (end, next) = scanner.scan(b"=;:", false)?;
+            tokens.push(Token::ParamName(start..end));
+            delimiter = next;
+            if delimiter == Some(b'=') {
+                scanner.position += 1;
+                let start = scanner.position;
+                let (end, next) = scanner.scan(b";:", true)?;
+                tokens.push(Token::ParamValue(start..end));
+                delimiter = next;
+            }
+        }
+
+        if delimiter != Some(b':') {
+            return Err(Error::DelimiterExpected);
+        }
+        let start = scanner.position;
+        scanner.position += 1;
+        tokens.push(Token::ValueSeparator(start..scanner.position));
+
+        let start = scanner.position;
+        let (end, _) = scanner.scan(&[], false)?;
+        if end > start {
+            tokens.push(Token::Value(start..end));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Scans the bytes of a content line.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Scanner<'_> {
+    fn at_end(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    /// Length of the line break at the current position.
+    fn line_break(&self) -> Option<usize> {
+        match line_break(&self.bytes[self.position..]) {
+            0 => None,
+            len => Some(len),
+        }
+    }
+
+    /// Advance to the next delimiter or the end of the content line.
+    ///
+    /// Folded lines are skipped and delimiters inside double quotes
+    /// are ignored when `quotes` is set. Returns the end of the scanned
+    /// range and the delimiter that was found, offsets in errors are
+    /// relative to the start of the scanned range.
+    fn scan(
+        &mut self,
+        delimiters: &[u8],
+        quotes: bool,
+    ) -> Result<(usize, Option<u8>)> {
+        let start = self.position;
+        let mut quoted = None;
+        while !self.at_end() {
+            if let Some(len) = self.line_break() {
+                if matches!(
+                    self.bytes.get(self.position + len),
+                    Some(b' ' | b'\t')
+                ) {
+                    self.position += len + 1;
+                    continue;
+                }
+                break;
+            }
+            let byte = self.bytes[self.position];
+            if byte.is_ascii_control() && byte != b'\t' {
+                return Err(LexError::UnexpectedControlChar(
+                    byte as char,
+                    self.position - start,
+                )
+                .into());
+            }
+            if quotes && byte == b'"' {
+                quoted = match quoted {
+                    Some(_) => None,
+                    None => Some(self.position),
+                };
+            } else if quoted.is_none() && delimiters.contains(&byte) {
+                return Ok((self.position, Some(byte)));
+            }
+            self.position += 1;
+        }
+        if let Some(quote) = quoted {
+            return Err(LexError::UnterminatedQuote(quote - start).into());
+        }
+        Ok((self.position, None))
+    }
+}
@@ -0,0 +1,175 @@
+//! Conversion to the vCard 3.0 text format (RFC 2426).
+use std::fmt::Write;
+
+use crate::{
+    escape_value,
+    name::*,
+    parameter::{Parameters, RelatedType, TypeParameter, ValueType},
+    property::*,
+    vcard::{fold_line, qualified_name},
+    Error, Result, Vcard, Version,
+};
+
+/// Property name used by address book servers for the KIND.
+const X_KIND: &str = "X-ADDRESSBOOKSERVER-KIND";
+
+/// Convert a vCard to version 3.0.
+pub(crate) fn to_version_3(card: &Vcard) -> Result<String> {
+    let mut out = String::new();
+    let _ = write!(out, "{}\r\n{}:{}\r\n", BEGIN, VERSION, Version::V3);
+
+    // N is required in 3.0
+    if card.name.is_none() {
+        out.push_str("N:;;;;\r\n");
+    }
+
+    for (kind, index) in card.property_order() {
+        let (name, prop) = match card.property(kind, index) {
+            Some(entry) => entry,
+            None => continue,
+        };
+        let mut params = prop.parameters().cloned().unwrap_or_default();
+        let mut value = prop.to_string();
+
+        let name = match kind {
+            PropertyKind::Kind => match &card.kind {
+                Some(KindProperty {
+                    value: Kind::Individual,
+                    ..
+                }) => continue,
+                _ => X_KIND,
+            },
+            PropertyKind::Geo => {
+                let coords = card.geo[index]
+                    .coordinates()
+                    .map_err(|_| Error::Unrepresentable(name.to_string()))?;
+                value = format!("{};{}", coords.latitude, coords.longitude);
+                GEO
+            }
+            PropertyKind::Related => {
+                let agent = TypeParameter::Related(RelatedType::Agent);
+                let types = params
+                    .types
+                    .as_mut()
+                    .filter(|types| types.contains(&agent))
+                    .ok_or_else(|| {
+                        Error::Unrepresentable(name.to_string())
+                    })?;
+                types.retain(|value| value != &agent);
+                if types.is_empty() {
+                    params.types = None;
+                }
+                if let TextOrUriProperty::Uri(_) = &card.related[index] {
+                    params.value = Some(ValueType::Uri);
+                }
+                AGENT
+            }
+            PropertyKind::Gender => "X-GENDER",
+            PropertyKind::Anniversary => "X-ANNIVERSARY",
+            PropertyKind::Member
+            | PropertyKind::Lang
+            | PropertyKind::Xml
+            | PropertyKind::ClientPidMap => {
+                return Err(Error::Unrepresentable(name.to_string()));
+            }
+            _ => name,
+        };
+
+        // The ADR label is a separate property in 3.0
+        let label = params.label.take().map(|label| {
+            let mut label_params: Parameters = Default::default();
+            label_params.types.clone_from(&params.types);
+            label_params.pref = params.pref;
+            (label_params, label)
+        });
+
+        let line = format!(
+            "{}{}:{}",
+            qualified_name(prop, name),
+            parameters(name, params)?,
+            value
+        );
+        let _ = write!(out, "{}\r\n", fold_line(line, 75));
+
+        if let Some((label_params, label)) = label {
+            let line = format!(
+                "{}{}:{}",
+                qualified_name(prop, LABEL),
+                parameters(LABEL, label_params)?,
+                escape_value(&label, false)
+            );
+            let _ = write!(out, "{}\r\n", fold_line(line, 75));
+        }
+    }
+
+    let _ = write!(out, "{}\r\n", END);
+    Ok(out)
+}
+
+/// Format parameters for version 3.0.
+///
+/// PREF is expressed as a TYPE value, TYPE values are written
+/// in upper case and the MEDIATYPE of PHOTO, LOGO, SOUND and
+/// KEY is written as a TYPE value using the media subtype (eg:
+/// `TYPE=JPEG`). CALSCALE is omitted when it is `gregorian`;
+/// other parameters that only exist in 4.0 (PID, ALTID, SORT-AS,
+/// CALSCALE, GEO, TZ and MEDIATYPE for other properties) are an
+/// error.
+fn parameters(name: &str, mut params: Parameters) -> Result<String> {
+    if params
+        .calscale
+        .as_ref()
+        .is_some_and(|value| value.eq_ignore_ascii_case("gregorian"))
+    {
+        params.calscale = None;
+    }
+
+    let media_type = params.media_type.take().map(|value| value.to_string());
+    let unrepresentable = [
+        (PID, params.pid.is_some()),
+        (ALTID, params.alt_id.is_some()),
+        (SORT_AS, params.sort_as.is_some()),
+        (CALSCALE, params.calscale.is_some()),
+        (GEO, params.geo.is_some()),
+        (TZ, params.timezone.is_some()),
+        (
+            MEDIATYPE,
+            media_type.is_some()
+                && ![PHOTO, LOGO, SOUND, KEY].contains(&name),
+        ),
+    ];
+    if let Some((param, _)) =
+        unrepresentable.iter().find(|(_, present)| *present)
+    {
+        return Err(Error::Unrepresentable(format!("{};{}", name, param)));
+    }
+
+    let mut types = params
+        .types
+        .take()
+        .unwrap_or_default()
+        .iter()
+        .map(|value| value.to_string().to_uppercase())
+        .collect::<Vec<_>>();
+    if let Some(media_type) = media_type {
+        let subtype = media_type
+            .split(';')
+            .next()
+            .and_then(|essence| essence.split('/').nth(1))
+            .unwrap_or_default()
+            .trim()
+            .to_uppercase();
+        if !subtype.is_empty() && !types.contains(&subtype) {
+            types.push(subtype);
+        }
+    }
+    if params.pref.take().is_some() && !types.iter().any(|t| t == "PREF") {
+        types.push(String::from("PREF"));
+    }
+
+    let mut out = params.to_string();
+    if !types.is_empty() {
+        let _ = write!(out, ";{}={}", TYPE, types.join(","));
+    }
+    Ok(out)
+}
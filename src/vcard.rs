@@ -1,6 +1,13 @@
 //! Definition of a single vCard.
 
-use std::{borrow::Cow, fmt};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    io::BufRead,
+    str::FromStr,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -8,9 +15,71 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "zeroize")]
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(feature = "language-tags")]
+use language_tags::LanguageTag;
+
 use base64::{engine::general_purpose, Engine};
 
-use crate::{iter, property::*, Error, Result};
+use crate::{
+    iter,
+    name::BOM,
+    parameter::{allows_type_parameter, Pref},
+    parser::{unfold_with_folds, valid_group, VcardParser},
+    property::*,
+    Error, ParseOptions, Result, VcardDiff, VcardReader, Warning,
+};
+
+/// Versions of the vCard format.
+///
+/// Cards are always represented using the 4.0 model, version 3.0
+/// input is translated when parsed.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Version {
+    /// vCard 3.0 as defined by RFC 2426.
+    V3,
+    /// vCard 4.0 as defined by RFC 6350.
+    V4,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::V3 => "3.0",
+                Self::V4 => "4.0",
+            }
+        )
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "3.0" => Ok(Self::V3),
+            "4.0" => Ok(Self::V4),
+            _ => Err(Error::UnsupportedVersion(s.to_string())),
+        }
+    }
+}
+
+/// Identity of a vCard.
+///
+/// Two vCards with the same identity describe the same entity
+/// even when their other properties differ; use it as the key of
+/// a `HashSet` or `HashMap` to collect vCards by identity.
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub enum Identity {
+    /// Value of the UID property.
+    Uid(String),
+    /// Canonical text of a vCard without a UID.
+    Content(String),
+}
 
 /// The vCard type.
 #[derive(Debug, Default, Eq, PartialEq, Clone)]
@@ -49,13 +118,13 @@ pub struct Vcard {
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub name: Option<TextListProperty>,
+    pub name: Option<NameProperty>,
     /// Value of the NICKNAME property.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
-    pub nickname: Vec<TextProperty>,
+    pub nickname: Vec<TextListProperty>,
     /// Value of the PHOTO property.
     ///
     /// Note that the spec says this should be a URI but certain
@@ -149,7 +218,7 @@ pub struct Vcard {
         feature = "serde",
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
-    pub org: Vec<TextListProperty>,
+    pub org: Vec<OrgProperty>,
     /// Value of the MEMBER property.
     #[cfg_attr(
         feature = "serde",
@@ -251,14 +320,46 @@ pub struct Vcard {
     pub cal_uri: Vec<UriProperty>,
 
     // Extensions
-    /// Private property extensions (`X-`).
+    /// Private property extensions (`X-`) and unknown IANA properties.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
     pub extensions: Vec<ExtensionProperty>,
+
+    /// Order of the properties when parsed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    pub(crate) order: PropertyOrder,
+}
+
+/// Order in which properties were parsed.
+///
+/// Each entry is the kind of property and the index into the
+/// collection for that kind. Ordering does not affect equality.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PropertyOrder {
+    entries: Vec<(PropertyKind, usize)>,
+    counts: HashMap<PropertyKind, usize>,
+}
+
+impl PropertyOrder {
+    /// Record the next property of a kind.
+    pub(crate) fn push(&mut self, kind: PropertyKind) {
+        let count = self.counts.entry(kind).or_default();
+        self.entries.push((kind, *count));
+        *count += 1;
+    }
+}
+
+impl PartialEq for PropertyOrder {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
+impl Eq for PropertyOrder {}
+
 impl Vcard {
     /// Create a new vCard with the given formatted name.
     pub fn new(formatted_name: String) -> Self {
@@ -267,7 +368,539 @@ impl Vcard {
         card
     }
 
+    /// Values of the FN property.
+    pub fn formatted_names(&self) -> &[TextProperty] {
+        &self.formatted_name
+    }
+
+    /// Value of the N property.
+    pub fn name(&self) -> Option<&NameProperty> {
+        self.name.as_ref()
+    }
+
+    /// Values of the NICKNAME property.
+    pub fn nicknames(&self) -> &[TextListProperty] {
+        &self.nickname
+    }
+
+    /// Value of the BDAY property.
+    pub fn birthday(&self) -> Option<&DateTimeOrTextProperty> {
+        self.bday.as_ref()
+    }
+
+    /// Values of the ADR property.
+    pub fn addresses(&self) -> &[AddressProperty] {
+        &self.address
+    }
+
+    /// Values of the TEL property.
+    pub fn telephones(&self) -> &[TextOrUriProperty] {
+        &self.tel
+    }
+
+    /// Values of the EMAIL property.
+    ///
+    /// ```
+    /// use vcard4::parse;
+    /// let input = r#"BEGIN:VCARD
+    /// VERSION:4.0
+    /// FN:Jane Doe
+    /// EMAIL:jane@example.com
+    /// END:VCARD"#;
+    /// let card = parse(input).unwrap().remove(0);
+    /// let email = card.emails().first().unwrap();
+    /// assert_eq!("jane@example.com", email.value);
+    /// ```
+    pub fn emails(&self) -> &[TextProperty] {
+        &self.email
+    }
+
+    /// Values of the URL property.
+    pub fn urls(&self) -> &[UriProperty] {
+        &self.url
+    }
+
+    /// Values of the TITLE property.
+    pub fn titles(&self) -> &[TextProperty] {
+        &self.title
+    }
+
+    /// Values of the ORG property.
+    pub fn organizations(&self) -> &[OrgProperty] {
+        &self.org
+    }
+
+    /// Values of the NOTE property.
+    pub fn notes(&self) -> &[TextProperty] {
+        &self.note
+    }
+
+    /// Values of all the CATEGORIES properties.
+    ///
+    /// Categories are merged across properties in order and
+    /// duplicates are removed case-insensitively, the first
+    /// occurrence of a category is retained.
+    pub fn categories(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.categories
+            .iter()
+            .flat_map(|prop| prop.value.iter())
+            .filter(|value| seen.insert(value.to_lowercase()))
+            .map(|value| value.as_str())
+            .collect()
+    }
+
+    /// Value of the UID property.
+    pub fn uid(&self) -> Option<&TextOrUriProperty> {
+        self.uid.as_ref()
+    }
+
+    /// UUID of the UID property when it is a `urn:uuid:` URN.
+    ///
+    /// Returns `None` when there is no UID property, it is not
+    /// a UUID URN or the UUID is malformed.
+    #[cfg(feature = "uuid")]
+    pub fn uuid(&self) -> Option<uuid::Uuid> {
+        const PREFIX: &str = "urn:uuid:";
+        let value = self.uid.as_ref()?.to_string();
+        let prefix = value.get(..PREFIX.len())?;
+        if !prefix.eq_ignore_ascii_case(PREFIX) {
+            return None;
+        }
+        uuid::Uuid::try_parse(&value[PREFIX.len()..]).ok()
+    }
+
+    /// Parse every vCard in a stream of concatenated vCards.
+    ///
+    /// The input is split on the `BEGIN:VCARD` and `END:VCARD`
+    /// framing and each vCard is parsed separately; errors report
+    /// the index of the vCard in the stream.
+    pub fn parse_many(input: &str) -> Result<Vec<Self>> {
+        let (source, folds) = unfold_with_folds(input);
+        let mut cards = Vec::new();
+        let mut start: Option<(usize, usize)> = None;
+        let mut offset = 0;
+        let mut fold_index = 0;
+        for (index, line) in source.split_inclusive('\n').enumerate() {
+            // Folds before this line count towards its line number
+            while folds.get(fold_index).is_some_and(|fold| *fold <= offset) {
+                fold_index += 1;
+            }
+            let content = line.trim_start_matches(BOM).trim_end();
+            if content.eq_ignore_ascii_case("BEGIN:VCARD") {
+                if start.is_some() {
+                    return Err(Error::UnterminatedCard(cards.len()));
+                }
+                start = Some((offset, index + 1 + fold_index));
+            } else if content.eq_ignore_ascii_case("END:VCARD") {
+                let (begin, first_line) = start
+                    .take()
+                    .ok_or(Error::UnexpectedContent(cards.len()))?;
+                let end = offset + line.trim_end().len();
+                let first = folds.partition_point(|fold| *fold < begin);
+                let last = folds.partition_point(|fold| *fold < end);
+                let card_folds: Vec<usize> = folds[first..last]
+                    .iter()
+                    .map(|fold| fold - begin)
+                    .collect();
+                let mut parser = VcardParser::new(&source[begin..end], true)
+                    .with_folds(&card_folds, first_line);
+                let card =
+                    parser.parse_first().map_err(|e| Error::InvalidCard {
+                        index: cards.len(),
+                        source: Box::new(e),
+                    })?;
+                cards.push(card);
+            } else if start.is_none() && !content.is_empty() {
+                return Err(Error::UnexpectedContent(cards.len()));
+            }
+            offset += line.len();
+        }
+        if start.is_some() {
+            return Err(Error::UnterminatedCard(cards.len()));
+        }
+        Ok(cards)
+    }
+
+    /// Parse vCards lazily from a reader.
+    ///
+    /// Each vCard is parsed when its `END:VCARD` line is read
+    /// so memory use is bounded by the size of a single vCard.
+    pub fn from_reader<R: BufRead>(reader: R) -> VcardReader<R> {
+        VcardReader::new(reader)
+    }
+
+    /// Parse the first vCard in the input using the given options.
+    ///
+    /// Returns the vCard along with any warnings for problems that
+    /// were tolerated when parsing in lenient mode.
+    pub fn parse_with<S: AsRef<str>>(
+        input: S,
+        options: ParseOptions,
+    ) -> Result<(Self, Vec<Warning>)> {
+        let (source, folds) = unfold_with_folds(input.as_ref());
+        let mut parser =
+            VcardParser::with_options(&source, options).with_folds(&folds, 1);
+        let card = parser.parse_first()?;
+        Ok((card, parser.into_warnings()))
+    }
+
+    /// Parse the first vCard in the input using the given options.
+    ///
+    /// Returns the vCard along with the line number and error for
+    /// each property that was skipped when the
+    /// `skip_invalid_properties` option is enabled.
+    pub fn parse_skipping<S: AsRef<str>>(
+        input: S,
+        options: ParseOptions,
+    ) -> Result<(Self, Vec<(usize, Error)>)> {
+        let (source, folds) = unfold_with_folds(input.as_ref());
+        let mut parser =
+            VcardParser::with_options(&source, options).with_folds(&folds, 1);
+        let card = parser.parse_first()?;
+        Ok((card, parser.into_skipped()))
+    }
+
+    /// Convert this vCard to jCard.
+    #[cfg(feature = "serde")]
+    pub fn to_jcard(&self) -> serde_json::Value {
+        crate::jcard::to_jcard(self)
+    }
+
+    /// Create a vCard from jCard.
+    #[cfg(feature = "serde")]
+    pub fn from_jcard(value: &serde_json::Value) -> Result<Self> {
+        crate::jcard::from_jcard(value)
+    }
+
+    /// Convert this vCard to xCard.
+    pub fn to_xcard(&self) -> String {
+        crate::xcard::to_xcard(self)
+    }
+
+    /// Convert this vCard to text for a version.
+    ///
+    /// When converting to 3.0 the GEO value is written as
+    /// `lat;lon`, KIND is written as an extension property, PREF
+    /// and MEDIATYPE are expressed as TYPE values and the ADR
+    /// LABEL is written as a LABEL property; properties and
+    /// parameters that have no 3.0 equivalent are an error.
+    pub fn to_version(&self, version: Version) -> Result<String> {
+        match version {
+            Version::V3 => crate::v3::to_version_3(self),
+            Version::V4 => Ok(self.to_string()),
+        }
+    }
+
+    /// Canonical text for this vCard.
+    ///
+    /// Content lines are sorted by property name and then by
+    /// their text, TYPE values and extension parameters are
+    /// sorted, names are upper case and lines are not folded.
+    /// Cards that only differ in the order of properties or
+    /// parameters produce the same output which makes it
+    /// suitable for comparison or signing.
+    pub fn canonicalize(&self) -> String {
+        use crate::name::*;
+        let mut lines = Vec::new();
+        for kind in PropertyKind::ALL {
+            let mut index = 0;
+            while let Some((name, prop)) = self.property(kind, index) {
+                lines.push(canonical_line(prop, name));
+                index += 1;
+            }
+        }
+        lines.sort();
+
+        let mut output = format!("{}\r\n{}\r\n", BEGIN, VERSION_4);
+        for (_, line) in lines {
+            output.push_str(&line);
+            output.push_str("\r\n");
+        }
+        output.push_str(END);
+        output.push_str("\r\n");
+        output
+    }
+
+    /// Get a property by kind and index.
+    ///
+    /// Returns the property name and the property.
+    pub fn property(
+        &self,
+        kind: PropertyKind,
+        index: usize,
+    ) -> Option<(&str, &dyn Property)> {
+        fn single<T>(value: &Option<T>, index: usize) -> Option<&T> {
+            value.as_ref().filter(|_| index == 0)
+        }
+
+        let prop: &dyn Property = match kind {
+            PropertyKind::Source => self.source.get(index)?,
+            PropertyKind::Kind => single(&self.kind, index)?,
+            PropertyKind::Xml => self.xml.get(index)?,
+            PropertyKind::FormattedName => self.formatted_name.get(index)?,
+            PropertyKind::Name => single(&self.name, index)?,
+            PropertyKind::Nickname => self.nickname.get(index)?,
+            PropertyKind::Photo => self.photo.get(index)?,
+            PropertyKind::Birthday => single(&self.bday, index)?,
+            PropertyKind::Anniversary => single(&self.anniversary, index)?,
+            PropertyKind::Gender => single(&self.gender, index)?,
+            PropertyKind::Url => self.url.get(index)?,
+            PropertyKind::Address => self.address.get(index)?,
+            PropertyKind::Title => self.title.get(index)?,
+            PropertyKind::Role => self.role.get(index)?,
+            PropertyKind::Logo => self.logo.get(index)?,
+            PropertyKind::Org => self.org.get(index)?,
+            PropertyKind::Member => self.member.get(index)?,
+            PropertyKind::Related => self.related.get(index)?,
+            PropertyKind::Telephone => self.tel.get(index)?,
+            PropertyKind::Email => self.email.get(index)?,
+            PropertyKind::Impp => self.impp.get(index)?,
+            PropertyKind::Lang => self.lang.get(index)?,
+            PropertyKind::TimeZone => self.timezone.get(index)?,
+            PropertyKind::Geo => self.geo.get(index)?,
+            PropertyKind::Categories => self.categories.get(index)?,
+            PropertyKind::Note => self.note.get(index)?,
+            PropertyKind::ProdId => single(&self.prod_id, index)?,
+            PropertyKind::Rev => single(&self.rev, index)?,
+            PropertyKind::Sound => self.sound.get(index)?,
+            PropertyKind::Uid => single(&self.uid, index)?,
+            PropertyKind::ClientPidMap => self.client_pid_map.get(index)?,
+            PropertyKind::Key => self.key.get(index)?,
+            PropertyKind::FreeBusyUrl => self.fburl.get(index)?,
+            PropertyKind::CalendarAddressUri => {
+                self.cal_adr_uri.get(index)?
+            }
+            PropertyKind::CalendarUri => self.cal_uri.get(index)?,
+            PropertyKind::Extension => {
+                let prop = self.extensions.get(index)?;
+                return Some((&prop.name, prop));
+            }
+        };
+        Some((kind.name()?, prop))
+    }
+
+    /// Get the kind and index of every property in the order
+    /// they should be written.
+    ///
+    /// Properties are in the order they were parsed, any
+    /// properties added afterwards follow in the default order.
+    ///
+    /// The parsed order refers to properties by their index in
+    /// each collection so it goes stale when the public fields are
+    /// mutated; removing or reordering elements changes which
+    /// property an index refers to.
+    pub fn property_order(&self) -> Vec<(PropertyKind, usize)> {
+        let mut seen = HashSet::new();
+        let mut order = Vec::with_capacity(self.order.entries.len());
+        for entry in &self.order.entries {
+            if self.property(entry.0, entry.1).is_some()
+                && seen.insert(*entry)
+            {
+                order.push(*entry);
+            }
+        }
+        for kind in PropertyKind::ALL {
+            let mut index = 0;
+            while self.property(kind, index).is_some() {
+                if seen.insert((kind, index)) {
+                    order.push((kind, index));
+                }
+                index += 1;
+            }
+        }
+        order
+    }
+
+    /// Get all the properties that belong to a group.
+    ///
+    /// Group names are compared case-insensitively and the
+    /// properties are in document order.
+    pub fn group<'a>(&'a self, name: &str) -> Vec<&'a dyn Property> {
+        self.property_order()
+            .into_iter()
+            .filter_map(|(kind, index)| self.property(kind, index))
+            .map(|(_, prop)| prop)
+            .filter(|prop| {
+                prop.group()
+                    .map(|group| group.eq_ignore_ascii_case(name))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Get the properties of a kind sorted by preference.
+    ///
+    /// Properties are sorted by ascending PREF parameter and
+    /// properties without a PREF come last; ties keep the
+    /// document order.
+    pub fn preferred(&self, kind: PropertyKind) -> Vec<&dyn Property> {
+        let mut props = self
+            .property_order()
+            .into_iter()
+            .filter(|(entry, _)| *entry == kind)
+            .filter_map(|(kind, index)| self.property(kind, index))
+            .map(|(_, prop)| prop)
+            .collect::<Vec<_>>();
+        props.sort_by_key(|prop| {
+            let pref = prop.pref();
+            (pref.is_none(), pref)
+        });
+        props
+    }
+
+    /// Get the alternative representations sharing an ALTID.
+    ///
+    /// Properties are returned in the order they are written.
+    pub fn altid_group<'a>(&'a self, id: &str) -> Vec<&'a dyn Property> {
+        self.property_order()
+            .into_iter()
+            .filter_map(|(kind, index)| self.property(kind, index))
+            .map(|(_, prop)| prop)
+            .filter(|prop| {
+                prop.parameters()
+                    .and_then(|params| params.alt_id.as_deref())
+                    == Some(id)
+            })
+            .collect()
+    }
+
+    /// Choose the property that best matches the wanted languages.
+    ///
+    /// Implements the lookup scheme from
+    /// [RFC 4647](https://www.rfc-editor.org/rfc/rfc4647#section-3.4)
+    /// using the LANGUAGE parameter of each property; when no
+    /// language matches the first property without a LANGUAGE
+    /// parameter is returned.
+    #[cfg(feature = "language-tags")]
+    pub fn best_by_language<'a>(
+        &'a self,
+        props: &[&'a dyn Property],
+        wanted: &[LanguageTag],
+    ) -> Option<&'a dyn Property> {
+        let wanted = wanted.iter().map(|tag| tag.as_str());
+        lookup_language(props, wanted)
+    }
+
+    /// Choose the property that best matches the wanted languages.
+    ///
+    /// Implements the lookup scheme from
+    /// [RFC 4647](https://www.rfc-editor.org/rfc/rfc4647#section-3.4)
+    /// using the LANGUAGE parameter of each property; when no
+    /// language matches the first property without a LANGUAGE
+    /// parameter is returned.
+    #[cfg(not(feature = "language-tags"))]
+    pub fn best_by_language<'a>(
+        &'a self,
+        props: &[&'a dyn Property],
+        wanted: &[String],
+    ) -> Option<&'a dyn Property> {
+        let wanted = wanted.iter().map(|tag| tag.as_str());
+        lookup_language(props, wanted)
+    }
+
+    /// Get the values of a named parameter across all properties.
+    ///
+    /// Properties are visited in the order they are written and
+    /// list parameters yield one item for each value; text values
+    /// are borrowed from the properties.
+    pub fn parameters_named<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = Cow<'a, str>> + 'a {
+        let name = name.to_string();
+        self.property_order()
+            .into_iter()
+            .filter_map(move |(kind, index)| self.property(kind, index))
+            .filter_map(|(_, prop)| prop.parameters())
+            .flat_map(move |params| params.values(&name))
+    }
+
+    /// Merge another vCard describing the same entity into this vCard.
+    ///
+    /// Both vCards must have the same UID. Multi-valued properties
+    /// are combined skipping exact duplicates; for single-valued
+    /// properties and FN the instance with the higher preference
+    /// (lower PREF) is kept, this vCard wins when they are equal.
+    pub fn merge(&mut self, mut other: Vcard) -> Result<()> {
+        use std::mem::take;
+        let uid = |card: &Vcard| card.uid.as_ref().map(|uid| uid.to_string());
+        if uid(self).is_none() || uid(self) != uid(&other) {
+            return Err(Error::UidMismatch);
+        }
+
+        if !other.formatted_name.is_empty()
+            && (self.formatted_name.is_empty()
+                || rank_all(&other.formatted_name)
+                    < rank_all(&self.formatted_name))
+        {
+            self.formatted_name = take(&mut other.formatted_name);
+        }
+
+        prefer(&mut self.kind, other.kind.take());
+        prefer(&mut self.name, other.name.take());
+        prefer(&mut self.bday, other.bday.take());
+        prefer(&mut self.anniversary, other.anniversary.take());
+        prefer(&mut self.gender, other.gender.take());
+        prefer(&mut self.prod_id, other.prod_id.take());
+        prefer(&mut self.rev, other.rev.take());
+
+        union(&mut self.source, take(&mut other.source));
+        union(&mut self.xml, take(&mut other.xml));
+        union(&mut self.nickname, take(&mut other.nickname));
+        union(&mut self.photo, take(&mut other.photo));
+        union(&mut self.url, take(&mut other.url));
+        union(&mut self.address, take(&mut other.address));
+        union(&mut self.tel, take(&mut other.tel));
+        union(&mut self.email, take(&mut other.email));
+        union(&mut self.impp, take(&mut other.impp));
+        union(&mut self.lang, take(&mut other.lang));
+        union(&mut self.title, take(&mut other.title));
+        union(&mut self.role, take(&mut other.role));
+        union(&mut self.logo, take(&mut other.logo));
+        union(&mut self.org, take(&mut other.org));
+        union(&mut self.member, take(&mut other.member));
+        union(&mut self.related, take(&mut other.related));
+        union(&mut self.timezone, take(&mut other.timezone));
+        union(&mut self.geo, take(&mut other.geo));
+        union(&mut self.categories, take(&mut other.categories));
+        union(&mut self.note, take(&mut other.note));
+        union(&mut self.sound, take(&mut other.sound));
+        union(&mut self.client_pid_map, take(&mut other.client_pid_map));
+        union(&mut self.key, take(&mut other.key));
+        union(&mut self.fburl, take(&mut other.fburl));
+        union(&mut self.cal_adr_uri, take(&mut other.cal_adr_uri));
+        union(&mut self.cal_uri, take(&mut other.cal_uri));
+        union(&mut self.extensions, take(&mut other.extensions));
+        Ok(())
+    }
+
+    /// Identity of this vCard.
+    ///
+    /// The value of the UID property when present otherwise the
+    /// canonical text from `canonicalize()`.
+    pub fn identity(&self) -> Identity {
+        match &self.uid {
+            Some(uid) => Identity::Uid(uid.to_string()),
+            None => Identity::Content(self.canonicalize()),
+        }
+    }
+
+    /// Determine if this vCard has the same identity as another.
+    pub fn same_identity(&self, other: &Vcard) -> bool {
+        self.identity() == other.identity()
+    }
+
+    /// Get the changes required to turn this vCard into another.
+    pub fn diff(&self, other: &Vcard) -> VcardDiff {
+        crate::diff::diff(self, other)
+    }
+
     /// Validate this vCard.
+    ///
+    /// A formatted name is required and the MEMBER property is
+    /// only allowed when the KIND is `group`; member values are
+    /// always valid URIs as they are stored as a `Uri`.
     pub fn validate(&self) -> Result<()> {
         if self.formatted_name.is_empty() {
             return Err(Error::NoFormattedName);
@@ -284,6 +917,80 @@ impl Vcard {
         Ok(())
     }
 
+    /// Validate this vCard and collect every violation.
+    ///
+    /// In addition to the checks performed by [Vcard::validate]
+    /// the PREF, PID and TYPE parameters are verified for every
+    /// property; a PID source must refer to a CLIENTPIDMAP and
+    /// PID is not allowed on properties that may appear once.
+    ///
+    /// The cardinality of singleton properties is enforced by
+    /// the data model so it is not checked here.
+    pub fn validate_all(&self) -> Vec<Error> {
+        let mut errors = Vec::new();
+        if self.formatted_name.is_empty() {
+            errors.push(Error::NoFormattedName);
+        }
+        let is_group = matches!(
+            &self.kind,
+            Some(KindProperty {
+                value: Kind::Group,
+                ..
+            })
+        );
+        if !self.member.is_empty() && !is_group {
+            errors.push(Error::MemberRequiresGroup);
+        }
+
+        for (kind, index) in self.property_order() {
+            let (name, prop) = match self.property(kind, index) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            if let Some(group) = prop.group() {
+                if !valid_group(group) {
+                    errors.push(Error::InvalidGroup(group.clone()));
+                }
+            }
+
+            let params = match prop.parameters() {
+                Some(params) => params,
+                None => continue,
+            };
+
+            if let Some(pref) = params.pref {
+                if let Err(e) = Pref::new(pref) {
+                    errors.push(e);
+                }
+            }
+
+            if let Some(pids) = &params.pid {
+                if kind == PropertyKind::ClientPidMap {
+                    errors.push(Error::ClientPidMapPidNotAllowed);
+                } else {
+                    for pid in pids {
+                        let mapped = pid.source.is_none()
+                            || self.client_pid_map.iter().any(|map| {
+                                Some(map.value.source) == pid.source
+                            });
+                        if kind.is_singleton() || !mapped {
+                            errors.push(Error::InvalidPid(pid.to_string()));
+                        }
+                    }
+                }
+            }
+
+            if params.types.is_some()
+                && kind != PropertyKind::Extension
+                && !allows_type_parameter(name)
+            {
+                errors.push(Error::TypeParameter(name.to_string()));
+            }
+        }
+        errors
+    }
+
     /// Parse any embedded JPEG photos from the vCard photo property.
     ///
     /// This function looks for photo entries with an ENCODING
@@ -302,7 +1009,10 @@ impl Vcard {
                         (&params.types, &params.extensions)
                     {
                         if let (
-                            Some(TypeParameter::Extension(value)),
+                            Some(
+                                TypeParameter::Unknown(value)
+                                | TypeParameter::Extension(value),
+                            ),
                             Some((name, values)),
                         ) = (types.first(), extensions.first())
                         {
@@ -332,145 +1042,96 @@ impl TryFrom<&str> for Vcard {
     }
 }
 
+impl FromStr for Vcard {
+    type Err = Error;
+
+    /// Parse the first vCard using the default options.
+    ///
+    /// ```
+    /// use vcard4::Vcard;
+    /// let card = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:X\r\nEND:VCARD"
+    ///     .parse::<Vcard>()
+    ///     .unwrap();
+    /// assert_eq!("X", card.formatted_name[0].value);
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        let (card, _) = Self::parse_with(s, Default::default())?;
+        Ok(card)
+    }
+}
+
 impl fmt::Display for Vcard {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use crate::name::*;
         write!(f, "{}\r\n{}\r\n", BEGIN, VERSION_4)?;
 
-        // General
-        for val in &self.source {
-            write!(f, "{}\r\n", content_line(val, SOURCE))?;
-        }
-        for val in &self.kind {
-            write!(f, "{}\r\n", content_line(val, KIND))?;
-        }
-        for val in &self.xml {
-            write!(f, "{}\r\n", content_line(val, XML))?;
-        }
-
-        // Identification
-        for val in &self.formatted_name {
-            write!(f, "{}\r\n", content_line(val, FN))?;
-        }
-        for val in &self.name {
-            write!(f, "{}\r\n", content_line(val, N))?;
-        }
-        for val in &self.nickname {
-            write!(f, "{}\r\n", content_line(val, NICKNAME))?;
-        }
-        for val in &self.photo {
-            write!(f, "{}\r\n", content_line(val, PHOTO))?;
-        }
-        for val in &self.bday {
-            write!(f, "{}\r\n", content_line(val, BDAY))?;
-        }
-        for val in &self.anniversary {
-            write!(f, "{}\r\n", content_line(val, ANNIVERSARY))?;
-        }
-        for val in &self.gender {
-            write!(f, "{}\r\n", content_line(val, GENDER))?;
-        }
-        for val in &self.url {
-            write!(f, "{}\r\n", content_line(val, URL))?;
-        }
-
-        // Delivery Addressing
-        for val in &self.address {
-            write!(f, "{}\r\n", content_line(val, ADR))?;
-        }
-
-        // Organizational
-        for val in &self.title {
-            write!(f, "{}\r\n", content_line(val, TITLE))?;
-        }
-        for val in &self.role {
-            write!(f, "{}\r\n", content_line(val, ROLE))?;
-        }
-        for val in &self.logo {
-            write!(f, "{}\r\n", content_line(val, LOGO))?;
-        }
-        for val in &self.org {
-            write!(f, "{}\r\n", content_line(val, ORG))?;
-        }
-        for val in &self.member {
-            write!(f, "{}\r\n", content_line(val, MEMBER))?;
-        }
-        for val in &self.related {
-            write!(f, "{}\r\n", content_line(val, RELATED))?;
+        for (kind, index) in self.property_order() {
+            if let Some((name, prop)) = self.property(kind, index) {
+                write!(f, "{}\r\n", content_line(prop, name))?;
+            }
         }
 
-        // Communications
-        for val in &self.tel {
-            write!(f, "{}\r\n", content_line(val, TEL))?;
-        }
-        for val in &self.email {
-            write!(f, "{}\r\n", content_line(val, EMAIL))?;
-        }
-        for val in &self.impp {
-            write!(f, "{}\r\n", content_line(val, IMPP))?;
-        }
-        for val in &self.lang {
-            write!(f, "{}\r\n", content_line(val, LANG))?;
-        }
+        write!(f, "{}\r\n", END)
+    }
+}
 
-        // Geographic
-        for val in &self.timezone {
-            write!(f, "{}\r\n", content_line(val, TZ))?;
-        }
-        for val in &self.geo {
-            write!(f, "{}\r\n", content_line(val, GEO))?;
-        }
+/// Hash a vCard by identity.
+///
+/// The `identity()` is hashed so cards that share a UID hash the
+/// same and cards without a UID hash by their canonical text.
+///
+/// `PartialEq` is not identity based, it compares all properties
+/// so that equal cards always have equal hashes. Cards that share
+/// a UID but differ in other properties hash the same yet remain
+/// distinct, so a `HashSet` keeps both; key the set or map by the
+/// `identity()` to collect cards by identity and use
+/// `Vcard::diff()` to reconcile them.
+impl Hash for Vcard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
 
-        // Explanatory
-        for val in &self.categories {
-            write!(f, "{}\r\n", content_line(val, CATEGORIES))?;
-        }
-        for val in &self.note {
-            write!(f, "{}\r\n", content_line(val, NOTE))?;
-        }
-        for val in &self.prod_id {
-            write!(f, "{}\r\n", content_line(val, PRODID))?;
-        }
-        for val in &self.rev {
-            write!(f, "{}\r\n", content_line(val, REV))?;
-        }
-        for val in &self.sound {
-            write!(f, "{}\r\n", content_line(val, SOUND))?;
-        }
-        for val in &self.uid {
-            write!(f, "{}\r\n", content_line(val, UID))?;
-        }
-        for val in &self.client_pid_map {
-            write!(f, "{}\r\n", content_line(val, CLIENTPIDMAP))?;
-        }
+/// Rank a property by preference, lower is preferred and
+/// properties without a PREF rank last.
+fn rank(prop: &dyn Property) -> (bool, Option<Pref>) {
+    let pref = prop.pref();
+    (pref.is_none(), pref)
+}
 
-        // Security
-        for val in &self.key {
-            write!(f, "{}\r\n", content_line(val, KEY))?;
-        }
+/// Rank a list of properties by the most preferred property.
+fn rank_all<T: Property>(props: &[T]) -> (bool, Option<Pref>) {
+    props
+        .iter()
+        .map(|prop| rank(prop))
+        .min()
+        .unwrap_or((true, None))
+}
 
-        // Calendar
-        for val in &self.fburl {
-            write!(f, "{}\r\n", content_line(val, FBURL))?;
-        }
-        for val in &self.cal_adr_uri {
-            write!(f, "{}\r\n", content_line(val, CALADRURI))?;
-        }
-        for val in &self.cal_uri {
-            write!(f, "{}\r\n", content_line(val, CALURI))?;
+/// Replace a single-valued property when the value is preferred.
+fn prefer<T: Property>(target: &mut Option<T>, value: Option<T>) {
+    if let Some(value) = value {
+        let replace = match target {
+            Some(current) => rank(&value) < rank(current),
+            None => true,
+        };
+        if replace {
+            *target = Some(value);
         }
+    }
+}
 
-        // Private property extensions
-        for val in &self.extensions {
-            write!(f, "{}\r\n", content_line(val, &val.name))?;
+/// Append values that are not already present.
+fn union<T: PartialEq>(target: &mut Vec<T>, values: Vec<T>) {
+    for value in values {
+        if !target.contains(&value) {
+            target.push(value);
         }
-
-        write!(f, "{}\r\n", END)
     }
 }
 
 /// Get a content line.
-fn content_line(prop: &impl Property, prop_name: &str) -> String {
+fn content_line(prop: &dyn Property, prop_name: &str) -> String {
     let name = qualified_name(prop, prop_name);
 
     let params = if let Some(params) = prop.parameters() {
@@ -491,23 +1152,100 @@ fn content_line(prop: &impl Property, prop_name: &str) -> String {
     fold_line(line, 75)
 }
 
-fn fold_line(line: String, wrap_at: usize) -> String {
+/// Unfolded content line with normalized parameters.
+///
+/// Returns the upper case property name used for sorting
+/// together with the line.
+fn canonical_line(prop: &dyn Property, prop_name: &str) -> (String, String) {
+    let name = qualified_name(prop, prop_name).to_uppercase();
+
+    let params = if let Some(params) = prop.parameters() {
+        let mut params = params.clone();
+        if let Some(types) = params.types.as_mut() {
+            types.sort_by_key(|value| value.to_string());
+        }
+        if let Some(extensions) = params.extensions.as_mut() {
+            for (name, _) in extensions.iter_mut() {
+                *name = name.to_uppercase();
+            }
+            extensions.sort();
+        }
+        params.to_string()
+    } else {
+        String::new()
+    };
+
+    let line = format!("{}{}:{}", name, params, prop);
+    (prop_name.to_uppercase(), line)
+}
+
+/// Fold a content line so no physical line exceeds `wrap_at` octets.
+///
+/// Continuation lines start with a single space which counts towards
+/// the limit. Lines are only broken between grapheme clusters so a
+/// multi-byte UTF-8 sequence is never split.
+pub(crate) fn fold_line(line: String, wrap_at: usize) -> String {
     use unicode_segmentation::UnicodeSegmentation;
+    if line.len() <= wrap_at {
+        return line;
+    }
     let mut length = 0;
-    let mut folded_line = String::new();
+    let mut folded_line = String::with_capacity(line.len() + line.len() / 24);
     for grapheme in UnicodeSegmentation::graphemes(&line[..], true) {
-        length += grapheme.len();
-        if length % wrap_at == 0 {
+        if length > 0 && length + grapheme.len() > wrap_at {
             folded_line.push_str("\r\n ");
+            length = 1;
         }
+        length += grapheme.len();
         folded_line.push_str(grapheme);
     }
     folded_line
 }
 
+/// Find the first property matching a language range, removing
+/// subtags from the end of each range until a match is found.
+fn lookup_language<'a, 'b>(
+    props: &[&'a dyn Property],
+    wanted: impl Iterator<Item = &'b str>,
+) -> Option<&'a dyn Property> {
+    let language = |prop: &&dyn Property| {
+        prop.parameters()
+            .and_then(|params| params.language.as_ref())
+            .map(|tag| tag.to_string())
+    };
+
+    for range in wanted {
+        let mut range = range;
+        loop {
+            let found = props.iter().find(|prop| {
+                language(prop)
+                    .map(|tag| tag.eq_ignore_ascii_case(range))
+                    .unwrap_or(false)
+            });
+            if let Some(prop) = found {
+                return Some(*prop);
+            }
+            match range.rfind('-') {
+                Some(pos) => {
+                    range = &range[..pos];
+                    // Remove a trailing singleton subtag
+                    if range.len() >= 2
+                        && range.as_bytes()[range.len() - 2] == b'-'
+                    {
+                        range = &range[..range.len() - 2];
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    props.iter().find(|prop| language(prop).is_none()).copied()
+}
+
 /// Get the fully qualified name including any group.
-fn qualified_name<'a>(
-    prop: &impl Property,
+pub(crate) fn qualified_name<'a>(
+    prop: &dyn Property,
     prop_name: &'a str,
 ) -> Cow<'a, str> {
     if let Some(group) = prop.group() {
@@ -0,0 +1,403 @@
+//! Conversion to the XML representation of a vCard (RFC 6351).
+use std::fmt::Write;
+
+use crate::{
+    name::*,
+    parameter::{Parameters, TimeZoneParameter},
+    property::*,
+    Vcard,
+};
+
+/// Namespace for xCard elements.
+const NAMESPACE: &str = "urn:ietf:params:xml:ns:vcard-4.0";
+
+/// Child elements of a property in xCard.
+///
+/// Each element is a name and the text content for the element.
+type Elements = Vec<(&'static str, String)>;
+
+/// Value elements of a property in xCard.
+trait XCardValue: Property {
+    /// Get the elements for the property value.
+    fn xcard_value(&self) -> Elements;
+}
+
+/// Convert a vCard to xCard.
+pub(crate) fn to_xcard(card: &Vcard) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(xml, r#"<vcards xmlns="{}"><vcard>"#, NAMESPACE);
+
+    for (kind, index) in card.property_order() {
+        if let Some((name, prop)) = xcard_property(card, kind, index) {
+            property(&mut xml, prop, name);
+        }
+    }
+
+    xml.push_str("</vcard></vcards>");
+    xml
+}
+
+/// Get a property and its name by kind and index.
+///
+/// Mirrors `Vcard::property()` for the xCard values.
+fn xcard_property(
+    card: &Vcard,
+    kind: PropertyKind,
+    index: usize,
+) -> Option<(&str, &dyn XCardValue)> {
+    fn single<T>(value: &Option<T>, index: usize) -> Option<&T> {
+        value.as_ref().filter(|_| index == 0)
+    }
+
+    let prop: &dyn XCardValue = match kind {
+        PropertyKind::Source => card.source.get(index)?,
+        PropertyKind::Kind => single(&card.kind, index)?,
+        PropertyKind::Xml => card.xml.get(index)?,
+        PropertyKind::FormattedName => card.formatted_name.get(index)?,
+        PropertyKind::Name => single(&card.name, index)?,
+        PropertyKind::Nickname => card.nickname.get(index)?,
+        PropertyKind::Photo => card.photo.get(index)?,
+        PropertyKind::Birthday => single(&card.bday, index)?,
+        PropertyKind::Anniversary => single(&card.anniversary, index)?,
+        PropertyKind::Gender => single(&card.gender, index)?,
+        PropertyKind::Url => card.url.get(index)?,
+        PropertyKind::Address => card.address.get(index)?,
+        PropertyKind::Title => card.title.get(index)?,
+        PropertyKind::Role => card.role.get(index)?,
+        PropertyKind::Logo => card.logo.get(index)?,
+        PropertyKind::Org => card.org.get(index)?,
+        PropertyKind::Member => card.member.get(index)?,
+        PropertyKind::Related => card.related.get(index)?,
+        PropertyKind::Telephone => card.tel.get(index)?,
+        PropertyKind::Email => card.email.get(index)?,
+        PropertyKind::Impp => card.impp.get(index)?,
+        PropertyKind::Lang => card.lang.get(index)?,
+        PropertyKind::TimeZone => card.timezone.get(index)?,
+        PropertyKind::Geo => card.geo.get(index)?,
+        PropertyKind::Categories => card.categories.get(index)?,
+        PropertyKind::Note => card.note.get(index)?,
+        PropertyKind::ProdId => single(&card.prod_id, index)?,
+        PropertyKind::Rev => single(&card.rev, index)?,
+        PropertyKind::Sound => card.sound.get(index)?,
+        PropertyKind::Uid => single(&card.uid, index)?,
+        PropertyKind::ClientPidMap => card.client_pid_map.get(index)?,
+        PropertyKind::Key => card.key.get(index)?,
+        PropertyKind::FreeBusyUrl => card.fburl.get(index)?,
+        PropertyKind::CalendarAddressUri => card.cal_adr_uri.get(index)?,
+        PropertyKind::CalendarUri => card.cal_uri.get(index)?,
+        PropertyKind::Extension => {
+            let prop = card.extensions.get(index)?;
+            return Some((&prop.name, prop));
+        }
+    };
+    Some((kind.name()?, prop))
+}
+
+/// Write a property element.
+///
+/// Properties in a group are wrapped in a group element.
+fn property(xml: &mut String, prop: &dyn XCardValue, name: &str) {
+    let name = name.to_lowercase();
+    if let Some(group) = prop.group() {
+        let _ = write!(xml, r#"<group name="{}">"#, escape(group));
+    }
+    let _ = write!(xml, "<{}>", name);
+    if let Some(params) = prop.parameters() {
+        parameters(xml, params);
+    }
+    elements(xml, &prop.xcard_value());
+    let _ = write!(xml, "</{}>", name);
+    if prop.group().is_some() {
+        xml.push_str("</group>");
+    }
+}
+
+/// Write the parameters element.
+fn parameters(xml: &mut String, params: &Parameters) {
+    let mut children: Vec<(String, Elements)> = Vec::new();
+    if let Some(language) = &params.language {
+        children.push((
+            LANGUAGE.to_lowercase(),
+            vec![("language-tag", language.to_string())],
+        ));
+    }
+    if let Some(pref) = &params.pref {
+        children
+            .push((PREF.to_lowercase(), vec![("integer", pref.to_string())]));
+    }
+    if let Some(alt_id) = &params.alt_id {
+        children.push((ALTID.to_lowercase(), vec![("text", alt_id.clone())]));
+    }
+    if let Some(pids) = &params.pid {
+        children.push((PID.to_lowercase(), texts(pids)));
+    }
+    if let Some(types) = &params.types {
+        children.push((TYPE.to_lowercase(), texts(types)));
+    }
+    if let Some(media_type) = &params.media_type {
+        children.push((
+            MEDIATYPE.to_lowercase(),
+            vec![("text", media_type.to_string())],
+        ));
+    }
+    if let Some(calscale) = &params.calscale {
+        children.push((
+            CALSCALE.to_lowercase(),
+            vec![("text", calscale.clone())],
+        ));
+    }
+    if let Some(sort_as) = &params.sort_as {
+        children.push((SORT_AS.to_lowercase(), texts(sort_as)));
+    }
+    if let Some(geo) = &params.geo {
+        children.push((GEO.to_lowercase(), vec![("uri", geo.to_string())]));
+    }
+    if let Some(tz) = &params.timezone {
+        let value = match tz {
+            TimeZoneParameter::Text(val) => ("text", val.clone()),
+            TimeZoneParameter::Uri(val) => ("uri", val.to_string()),
+            TimeZoneParameter::UtcOffset(val) => {
+                ("text", UtcOffsetProperty::from(*val).to_string())
+            }
+        };
+        children.push((TZ.to_lowercase(), vec![value]));
+    }
+    if let Some(label) = &params.label {
+        children.push((LABEL.to_lowercase(), vec![("text", label.clone())]));
+    }
+    if let Some(extensions) = &params.extensions {
+        for (name, values) in extensions {
+            children.push((name.to_lowercase(), texts(values)));
+        }
+    }
+
+    if children.is_empty() {
+        return;
+    }
+    xml.push_str("<parameters>");
+    for (name, values) in children {
+        let _ = write!(xml, "<{}>", name);
+        elements(xml, &values);
+        let _ = write!(xml, "</{}>", name);
+    }
+    xml.push_str("</parameters>");
+}
+
+/// Write a list of elements with text content.
+fn elements(xml: &mut String, elements: &Elements) {
+    for (name, value) in elements {
+        if value.is_empty() {
+            let _ = write!(xml, "<{}/>", name);
+        } else {
+            let _ = write!(xml, "<{}>{}</{}>", name, escape(value), name);
+        }
+    }
+}
+
+/// Text elements for a list of values.
+fn texts(items: &[impl ToString]) -> Elements {
+    items.iter().map(|v| ("text", v.to_string())).collect()
+}
+
+/// Escape XML character data.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn date_and_or_time(value: &DateAndOrTime) -> (&'static str, String) {
+    let name = match value {
+        DateAndOrTime::Date(_) | DateAndOrTime::PartialDate(_) => "date",
+        DateAndOrTime::DateTime(_) | DateAndOrTime::ReducedDateTime(..) => {
+            "date-time"
+        }
+        DateAndOrTime::Time(_) => "time",
+    };
+    (name, value.to_string())
+}
+
+impl XCardValue for TextProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![("text", self.value.clone())]
+    }
+}
+
+impl XCardValue for TextListProperty {
+    fn xcard_value(&self) -> Elements {
+        texts(&self.value)
+    }
+}
+
+impl XCardValue for NameProperty {
+    fn xcard_value(&self) -> Elements {
+        const COMPONENTS: [&str; 5] =
+            ["surname", "given", "additional", "prefix", "suffix"];
+        let mut elements = Vec::new();
+        for (name, component) in
+            COMPONENTS.iter().zip(self.value.components())
+        {
+            if component.is_empty() {
+                elements.push((*name, String::new()));
+            }
+            for value in component {
+                elements.push((*name, value.clone()));
+            }
+        }
+        elements
+    }
+}
+
+impl XCardValue for OrgProperty {
+    fn xcard_value(&self) -> Elements {
+        texts(&self.value.components())
+    }
+}
+
+impl XCardValue for AddressProperty {
+    fn xcard_value(&self) -> Elements {
+        let value = &self.value;
+        let component = |v: &Option<String>| v.clone().unwrap_or_default();
+        vec![
+            ("pobox", component(&value.po_box)),
+            ("ext", component(&value.extended_address)),
+            ("street", component(&value.street_address)),
+            ("locality", component(&value.locality)),
+            ("region", component(&value.region)),
+            ("code", component(&value.postal_code)),
+            ("country", component(&value.country_name)),
+        ]
+    }
+}
+
+impl XCardValue for UriProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![("uri", self.value.to_string())]
+    }
+}
+
+impl XCardValue for KindProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![("text", self.value.to_string())]
+    }
+}
+
+impl XCardValue for GenderProperty {
+    fn xcard_value(&self) -> Elements {
+        let mut elements = vec![("sex", self.value.sex.to_string())];
+        if let Some(identity) = &self.value.identity {
+            elements.push(("identity", identity.clone()));
+        }
+        elements
+    }
+}
+
+impl XCardValue for LanguageProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![("language-tag", self.value.to_string())]
+    }
+}
+
+impl XCardValue for DateTimeProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![("timestamp", self.to_string())]
+    }
+}
+
+impl XCardValue for DateAndOrTimeProperty {
+    fn xcard_value(&self) -> Elements {
+        self.value.iter().map(date_and_or_time).collect()
+    }
+}
+
+impl XCardValue for UtcOffsetProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![("utc-offset", self.to_string())]
+    }
+}
+
+impl XCardValue for ClientPidMapProperty {
+    fn xcard_value(&self) -> Elements {
+        vec![
+            ("sourceid", self.value.source.to_string()),
+            ("uri", self.value.uri.to_string()),
+        ]
+    }
+}
+
+impl XCardValue for TextOrUriProperty {
+    fn xcard_value(&self) -> Elements {
+        match self {
+            Self::Text(val) => val.xcard_value(),
+            Self::Uri(val) => val.xcard_value(),
+        }
+    }
+}
+
+impl XCardValue for DateTimeOrTextProperty {
+    fn xcard_value(&self) -> Elements {
+        match self {
+            Self::DateTime(val) => val.xcard_value(),
+            Self::Text(val) => val.xcard_value(),
+        }
+    }
+}
+
+impl XCardValue for TimeZoneProperty {
+    fn xcard_value(&self) -> Elements {
+        match self {
+            Self::Text(val) => val.xcard_value(),
+            Self::Uri(val) => val.xcard_value(),
+            Self::UtcOffset(val) => val.xcard_value(),
+        }
+    }
+}
+
+impl XCardValue for ExtensionProperty {
+    fn xcard_value(&self) -> Elements {
+        match &self.value {
+            AnyProperty::Text(val) => vec![("text", val.clone())],
+            AnyProperty::Integer(val) => {
+                val.iter().map(|v| ("integer", v.to_string())).collect()
+            }
+            AnyProperty::Float(val) => {
+                val.iter().map(|v| ("float", v.to_string())).collect()
+            }
+            AnyProperty::Boolean(val) => vec![("boolean", val.to_string())],
+            AnyProperty::Date(val) => val
+                .iter()
+                .map(|v| date_and_or_time(&DateAndOrTime::Date(*v)))
+                .collect(),
+            AnyProperty::DateTime(val) => val
+                .iter()
+                .map(|v| date_and_or_time(&DateAndOrTime::DateTime(*v)))
+                .collect(),
+            AnyProperty::Time(val) => val
+                .iter()
+                .map(|v| date_and_or_time(&DateAndOrTime::Time(*v)))
+                .collect(),
+            AnyProperty::DateAndOrTime(val) => {
+                val.iter().map(date_and_or_time).collect()
+            }
+            AnyProperty::Timestamp(val) => val
+                .iter()
+                .map(|v| {
+                    ("timestamp", DateTimeProperty::from(*v).to_string())
+                })
+                .collect(),
+            AnyProperty::Uri(val) => vec![("uri", val.to_string())],
+            AnyProperty::UtcOffset(val) => {
+                vec![(
+                    "utc-offset",
+                    UtcOffsetProperty::from(*val).to_string(),
+                )]
+            }
+            AnyProperty::Language(val) => {
+                vec![("language-tag", val.to_string())]
+            }
+        }
+    }
+}
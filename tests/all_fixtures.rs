@@ -1,9 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use vcard4::parse;
-
-use test_helpers::assert_round_trip;
+use vcard4::{parse, ParseOptions, Vcard};
 
 #[test]
 fn parse_photo() -> Result<()> {
@@ -17,6 +15,15 @@ fn parse_photo() -> Result<()> {
     let jpegs = card.parse_photo_jpeg()?;
     assert_eq!(1, jpegs.len());
 
-    assert_round_trip(&card)?;
+    // Vendor specific types such as IPHONE are written back as they
+    // were given which strict parsing of the 4.0 output rejects
+    let encoded = card.to_string();
+    assert!(encoded.contains("\r\nTEL;PREF=1;TYPE=IPHONE,cell,voice:"));
+    let options = ParseOptions {
+        strict: false,
+        ..Default::default()
+    };
+    let (decoded, _) = Vcard::parse_with(&encoded, options)?;
+    assert_eq!(card, decoded);
     Ok(())
 }
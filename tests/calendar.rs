@@ -1,8 +1,14 @@
+#![allow(clippy::clone_on_copy, clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
 use test_helpers::{assert_media_type, assert_round_trip};
-use vcard4::parse;
+use vcard4::{
+    parameter::{Pid, Pref},
+    parse,
+    property::Property,
+};
 
 #[test]
 fn calendar_fburl() -> Result<()> {
@@ -83,3 +89,33 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn calendar_uri_pid() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+SOURCE;PID=1.1:https://example.com/directory/jdoe.vcf
+FBURL;PREF=1;PID=2.1:https://example.com/fb
+CALADRURI;PID=3.1:mailto:janedoe@example.com
+CALURI;PREF=2;PID=4.1:https://calendar.example.com/
+CLIENTPIDMAP:1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert!(card.validate_all().is_empty());
+
+    assert_eq!(Some(Pref::new(1)?), card.fburl[0].pref());
+    assert_eq!("https://example.com/fb", card.fburl[0].to_string());
+    assert_eq!(Some(Pref::new(2)?), card.cal_uri[0].pref());
+    for (prop, local) in [
+        (&card.source[0], 1),
+        (&card.fburl[0], 2),
+        (&card.cal_adr_uri[0], 3),
+        (&card.cal_uri[0], 4),
+    ] {
+        let pid = &prop.parameters.as_ref().unwrap().pid.as_ref().unwrap()[0];
+        assert_eq!(&Pid::new(local, Some(1)), pid);
+    }
+    assert_round_trip(&card)?;
+    Ok(())
+}
@@ -1,11 +1,14 @@
+#![allow(clippy::clone_on_copy, clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
 use test_helpers::{assert_language, assert_round_trip};
 use vcard4::{
-    parameter::{TelephoneType, TypeParameter},
+    parameter::{CommonType, Pref, TelephoneType, TypeParameter},
     parse,
-    property::TextOrUriProperty,
+    property::{Property, PropertyKind, TextOrUriProperty},
+    Error,
 };
 
 #[test]
@@ -81,6 +84,33 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+fn communications_tel_types() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+TEL;TYPE=voice,cell:+1-555-555-5555
+TEL;TYPE=work:+1-555-555-1234
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let prop = card.tel.first().unwrap();
+    assert_eq!(
+        vec![TelephoneType::Voice, TelephoneType::Cell],
+        prop.telephone_types()
+    );
+
+    let prop = card.tel.get(1).unwrap();
+    assert!(prop.telephone_types().is_empty());
+
+    assert_eq!("textphone", TelephoneType::TextPhone.to_string());
+    assert_eq!(TelephoneType::Fax, "FAX".parse::<TelephoneType>()?);
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
 #[test]
 fn communications_email() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -95,13 +125,99 @@ END:VCARD"#;
     let prop = card.email.get(0).unwrap();
 
     let types = prop.parameters.as_ref().unwrap().types.as_ref().unwrap();
-    assert_eq!(&TypeParameter::Work, types.get(0).unwrap());
+    assert_eq!(&TypeParameter::Work, types.first().unwrap());
+    assert_eq!(vec![CommonType::Work], prop.common_types());
 
     assert_eq!("jqpublic@xyz.example.com", &prop.value);
     assert_round_trip(&card)?;
     Ok(())
 }
 
+#[test]
+fn communications_email_types() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;TYPE=home,work,x-private:jane@example.com
+EMAIL:jane.doe@example.com
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert_eq!(
+        vec![
+            CommonType::Home,
+            CommonType::Work,
+            CommonType::Other("X-private".to_owned())
+        ],
+        card.email[0].common_types()
+    );
+    assert!(card.email[1].common_types().is_empty());
+    assert_eq!(CommonType::Work, "WORK".parse()?);
+    Ok(())
+}
+
+#[test]
+fn communications_email_pref() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;PREF=50:jane@example.com
+EMAIL:jane.doe@example.com
+EMAIL;TYPE=work;PREF=10:jdoe@example.org
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    assert_eq!(Some(Pref::new(50)?), card.email[0].pref());
+    assert_eq!(None, card.email[1].pref());
+
+    let preferred = card
+        .email
+        .iter()
+        .filter_map(|prop| prop.pref().map(|pref| (pref, prop)))
+        .min_by_key(|(pref, _)| *pref)
+        .map(|(_, prop)| prop)
+        .unwrap();
+    assert_eq!("jdoe@example.org", &preferred.value);
+
+    assert_eq!(10, "10".parse::<Pref>()?.get());
+    assert!(matches!(Pref::new(0), Err(Error::PrefOutOfRange(0))));
+    assert!(matches!(Pref::new(101), Err(Error::PrefOutOfRange(101))));
+    Ok(())
+}
+
+#[test]
+fn communications_email_preferred() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL:first@example.com
+EMAIL;PREF=2:second@example.com
+EMAIL:third@example.com
+EMAIL;PREF=1:fourth@example.com
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let emails = card
+        .preferred(PropertyKind::Email)
+        .into_iter()
+        .map(|prop| prop.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "fourth@example.com",
+            "second@example.com",
+            "first@example.com",
+            "third@example.com",
+        ],
+        emails
+    );
+    assert!(card.preferred(PropertyKind::Telephone).is_empty());
+    Ok(())
+}
+
 #[test]
 fn communications_impp() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -117,6 +233,7 @@ END:VCARD"#;
     let pref = prop.parameters.as_ref().unwrap().pref.clone().unwrap();
     assert_eq!(1, pref);
     assert_eq!("xmpp:alice@example.com", &prop.value.to_string());
+    assert_eq!("xmpp", prop.scheme());
     assert_round_trip(&card)?;
     Ok(())
 }
@@ -156,3 +273,31 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[cfg(feature = "phonenumber")]
+#[test]
+fn communications_tel_normalized() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+TEL:+1 (415) 555-2671
+TEL;VALUE=uri:tel:+1-415-555-2671
+TEL:(415) 555-2671
+TEL:not a number
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert_eq!(
+        Some("+14155552671".to_owned()),
+        card.tel[0].normalized_telephone("US")
+    );
+    assert_eq!(
+        Some("+14155552671".to_owned()),
+        card.tel[1].normalized_telephone("US")
+    );
+    assert_eq!(
+        Some("+14155552671".to_owned()),
+        card.tel[2].normalized_telephone("us")
+    );
+    assert_eq!(None, card.tel[3].normalized_telephone("US"));
+    Ok(())
+}
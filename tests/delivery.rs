@@ -1,8 +1,16 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
-use test_helpers::assert_round_trip;
-use vcard4::parse;
+use test_helpers::{assert_round_trip, strip_position};
+use vcard4::{
+    parameter::{AddressType, TimeZoneParameter, TypeParameter},
+    parse,
+    property::AddressProperty,
+    time::UtcOffset,
+    Error, VcardBuilder,
+};
 
 #[test]
 fn delivery_adr() -> Result<()> {
@@ -43,3 +51,182 @@ U.S.A."#,
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn delivery_adr_label() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;LABEL="Mr. John\n123 Main":;;123 Main;;;;
+ADR;LABEL="Mrs. Jane^n123 Main":;;123 Main;;;;
+ADR:;;123 Main;;;;
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    assert_eq!(Some("Mr. John\n123 Main"), card.address[0].label());
+    assert_eq!(Some("Mrs. Jane\n123 Main"), card.address[1].label());
+    assert_eq!(None, card.address[2].label());
+
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+NOTE;LABEL="Mr. John\n123 Main":Note
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::InvalidLabel(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn delivery_adr_to_label() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR:;;123 Main St;Springfield;IL;62704;USA
+ADR:PO Box 42;;;Springfield;;;
+ADR:;;;;;;
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    assert_eq!(
+        "123 Main St\nSpringfield, IL 62704\nUSA",
+        card.address[0].value.to_label()
+    );
+    assert_eq!("PO Box 42\nSpringfield", card.address[1].value.to_label());
+    assert_eq!("", card.address[2].value.to_label());
+    Ok(())
+}
+
+#[test]
+fn delivery_adr_geo() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;GEO="geo:37.386,-122.08":;;123 Main Street;Any Town;CA;91921;U.S.A.
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let prop = card.address.first().unwrap();
+    assert_eq!(Some((37.386, -122.08)), prop.geo());
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;GEO=geo:37.386,-122.08:;;123 Main Street;Any Town;CA;91921;U.S.A.
+END:VCARD"#;
+    let result = strip_position(parse(input));
+    assert!(matches!(result, Err(Error::NotQuoted(_))));
+    Ok(())
+}
+
+#[test]
+fn delivery_adr_tz() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;TZ=-0500:;;123 Main Street;Any Town;CA;91921;U.S.A.
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let prop = card.address.first().unwrap();
+    assert_eq!(
+        Some(&TimeZoneParameter::UtcOffset(UtcOffset::from_hms(
+            -5, 0, 0
+        )?)),
+        prop.tz()
+    );
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn delivery_adr_builder() -> Result<()> {
+    let prop = AddressProperty::builder()
+        .po_box("PO Box 1")
+        .extended_address("Suite 100")
+        .street("123 Main Street")
+        .locality("Any Town")
+        .region("CA")
+        .postal_code("91921-1234")
+        .country("U.S.A.")
+        .types(&[AddressType::Home, AddressType::Work])
+        .finish();
+    assert_eq!(
+        "PO Box 1;Suite 100;123 Main Street;Any Town;CA;91921-1234;U.S.A.",
+        prop.to_string()
+    );
+    assert_eq!(
+        &vec![TypeParameter::Home, TypeParameter::Work],
+        prop.parameters.as_ref().unwrap().types.as_ref().unwrap()
+    );
+
+    let mut card = VcardBuilder::new("Jane Doe".to_owned()).finish();
+    card.address.push(prop);
+    card.address.push(
+        AddressProperty::builder()
+            .locality("Any Town")
+            .types(&[AddressType::Home])
+            .finish(),
+    );
+    assert!(card
+        .to_string()
+        .contains("\r\nADR;TYPE=home:;;;Any Town;;;\r\n"));
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn delivery_adr_types() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;TYPE=home,work:;;123 Main Street;Any Town;CA;91921;U.S.A.
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let prop = card.address.first().unwrap();
+    assert_eq!(
+        vec![AddressType::Home, AddressType::Work],
+        prop.address_types()
+    );
+    assert_eq!("work", AddressType::Work.to_string());
+    assert_eq!(AddressType::Home, "HOME".parse()?);
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;TYPE=cell:;;123 Main Street;Any Town;CA;91921;U.S.A.
+END:VCARD"#;
+    let result = strip_position(parse(input));
+    assert!(matches!(result, Err(Error::UnknownAddressType(_))));
+    Ok(())
+}
+
+#[test]
+fn delivery_adr_escaped_semi_colon() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR:;;123 Main St\; Apt 4;Mock City\, North;;;
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let address = &card.address[0].value;
+    assert_eq!(
+        Some("123 Main St; Apt 4"),
+        address.street_address.as_deref()
+    );
+    assert_eq!(Some("Mock City, North"), address.locality.as_deref());
+    assert!(address.country_name.is_none());
+    assert!(card
+        .to_string()
+        .contains("ADR:;;123 Main St\\; Apt 4;Mock City\\, North;;;\r\n"));
+    assert_round_trip(&card)?;
+    Ok(())
+}
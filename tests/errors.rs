@@ -1,7 +1,11 @@
 mod test_helpers;
 
 use anyhow::Result;
-use vcard4::{helper::*, parameter::*, parse, property::ClientPidMap, Error};
+use std::io::Cursor;
+use test_helpers::strip_position;
+use vcard4::{
+    helper::*, parameter::*, parse, property::ClientPidMap, Error, Vcard,
+};
 
 #[test]
 fn error_empty() -> Result<()> {
@@ -21,7 +25,7 @@ fn error_wrong_token() -> Result<()> {
 #[test]
 fn error_no_version() -> Result<()> {
     let input = r#"BEGIN:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::TokenExpected)));
     Ok(())
 }
@@ -30,7 +34,7 @@ fn error_no_version() -> Result<()> {
 fn error_no_end() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::TokenExpected)));
     Ok(())
 }
@@ -53,7 +57,7 @@ fn error_parse_token_expected() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
 FN"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::TokenExpected)));
     Ok(())
 }
@@ -63,7 +67,7 @@ fn error_parse_delimiter_expected() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
 FN\,"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::DelimiterExpected)));
     Ok(())
 }
@@ -73,7 +77,7 @@ fn error_parse_pref_out_of_range() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
 FN;PREF=0:Jane Doe"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::PrefOutOfRange(_))));
     Ok(())
 }
@@ -85,7 +89,7 @@ VERSION:4.0
 FN:Jane Doe
 PRODID;TYPE=home:urn:uid:
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::TypeParameter(_))));
     Ok(())
 }
@@ -96,7 +100,7 @@ fn error_parse_geo_not_quoted() -> Result<()> {
 VERSION:4.0
 FN;GEO=geo:1\,2:Jane Doe
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::NotQuoted(_))));
     Ok(())
 }
@@ -107,7 +111,7 @@ fn error_parse_label_bad_prop() -> Result<()> {
 VERSION:4.0
 FN;LABEL=Jane:Jane Doe
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::InvalidLabel(_))));
     Ok(())
 }
@@ -117,7 +121,7 @@ fn error_parse_quoted_eof() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
 FN;GEO="urn:""#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::TokenExpected)));
     Ok(())
 }
@@ -127,7 +131,7 @@ fn error_parse_parameter_eof() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
 FN;GEO="#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::TokenExpected)));
     Ok(())
 }
@@ -137,7 +141,7 @@ fn error_parse_quoted_delimiter_expected() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
 FN;GEO="urn:"\,"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::DelimiterExpected)));
     Ok(())
 }
@@ -150,7 +154,7 @@ FN:Jane Doe
 N:Jane
 N:Doe
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::OnlyOnce(_))));
     Ok(())
 }
@@ -161,10 +165,18 @@ fn error_parse_bday_only_once() -> Result<()> {
 VERSION:4.0
 FN:Jane Doe
 BDAY:--0203
-BDAY:--0203
+bday:--0203
 END:VCARD"#;
-    let result = parse(input);
-    assert!(matches!(result, Err(Error::OnlyOnce(_))));
+    let result = strip_position(parse(input));
+    assert!(matches!(result, Err(Error::OnlyOnce(name)) if name == "BDAY"));
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL:jane@example.com
+EMAIL:jdoe@example.com
+END:VCARD"#;
+    assert_eq!(2, parse(input)?.remove(0).email.len());
     Ok(())
 }
 
@@ -176,7 +188,7 @@ FN:Jane Doe
 ANNIVERSARY:--0203
 ANNIVERSARY:--0203
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::OnlyOnce(_))));
     Ok(())
 }
@@ -189,7 +201,7 @@ FN:Jane Doe
 GENDER:M
 GENDER:F
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::OnlyOnce(_))));
     Ok(())
 }
@@ -202,7 +214,7 @@ FN:Jane Doe
 PRODID:Foo
 PRODID:Foo
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::OnlyOnce(_))));
     Ok(())
 }
@@ -215,7 +227,7 @@ FN:Jane Doe
 REV:19951031T222710Z
 REV:19951031T222710Z
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::OnlyOnce(_))));
     Ok(())
 }
@@ -228,7 +240,7 @@ FN:Jane Doe
 UID:foo
 UID:foo
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::OnlyOnce(_))));
     Ok(())
 }
@@ -240,7 +252,7 @@ VERSION:4.0
 FN:Jane Doe
 CLIENTPIDMAP;PID=1.1:1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::ClientPidMapPidNotAllowed)));
 
     // Trigger an else branch
@@ -261,7 +273,7 @@ VERSION:4.0
 FN:Jane Doe
 VERSION:4.0
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::VersionMisplaced)));
 
     let input = r#"BEGIN:VCARD
@@ -269,7 +281,7 @@ VERSION:4.0
 FN:Jane Doe
 VERSION:3.0
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::VersionMisplaced)));
     Ok(())
 }
@@ -281,7 +293,7 @@ VERSION:4.0
 FN:Jane Doe
 TZ;VALUE=date-time:Rayleigh/North America
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::UnsupportedValueType(_, _))));
     Ok(())
 }
@@ -293,7 +305,7 @@ VERSION:4.0
 FN:Jane Doe
 BDAY;VALUE=uri:https://example.com
 END:VCARD"#;
-    let result = parse(input);
+    let result = strip_position(parse(input));
     assert!(matches!(result, Err(Error::UnsupportedValueType(_, _))));
     Ok(())
 }
@@ -301,15 +313,65 @@ END:VCARD"#;
 #[test]
 fn error_control_character_value() -> Result<()> {
     let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\x7F\r\nEND:VCARD";
-    let result = parse(input);
-    assert!(matches!(result, Err(Error::ControlCharacter(_))));
+    let result = strip_position(parse(input));
+    assert!(matches!(result, Err(Error::ControlCharacter { .. })));
+
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nnote:A\x0Bnote\r\nEND:VCARD";
+    let result = strip_position(parse(input));
+    assert!(matches!(
+        result,
+        Err(Error::ControlCharacter { ref value, ref name, offset: 1 })
+            if value == "\\u{b}" && name == "NOTE"
+    ));
+
+    // Tabs are allowed in values and as folding whitespace
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nNOTE:A\tlong\r\n\tnote\r\nEND:VCARD";
+    let card = parse(input)?.remove(0);
+    assert_eq!("A\tlongnote", card.note[0].value);
     Ok(())
 }
 
 #[test]
 fn error_control_character_param() -> Result<()> {
     let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nADR;LABEL=label\x7F:;;;;;;\r\nEND:VCARD";
+    let result = strip_position(parse(input));
+    assert!(matches!(
+        result,
+        Err(Error::ControlCharacter { ref name, offset: 5, .. })
+            if name == "ADR"
+    ));
+    Ok(())
+}
+
+#[test]
+fn error_position() -> Result<()> {
+    let input = "BEGIN:VCARD\nVERSION:4.0\nTEL;PREF=101:+1-555-555-5555\nFN:Jane Doe\nEND:VCARD";
+    let result = parse(input);
+    assert!(matches!(
+        &result,
+        Err(Error::At { line: 3, column: 1, source })
+            if matches!(**source, Error::PrefOutOfRange(101))
+    ));
+    let message = result.err().unwrap().to_string();
+    assert!(message.starts_with("line 3, column 1: "));
+
+    // Folded lines are counted as physical lines
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nNOTE:A long\r\n  note\r\n  folded\r\nFN:Jane Doe\r\nFN:Jane Doe\r\n  Smith\r\nBDAY:--0203\r\nBDAY:--0203\r\nEND:VCARD";
     let result = parse(input);
-    assert!(matches!(result, Err(Error::ControlCharacter(_))));
+    assert!(matches!(
+        result,
+        Err(Error::At {
+            line: 10,
+            column: 1,
+            ..
+        })
+    ));
+
+    let result = Vcard::from_reader(Cursor::new(input)).next().unwrap();
+    assert!(matches!(
+        result,
+        Err(Error::InvalidCard { source, .. })
+            if matches!(*source, Error::At { line: 10, column: 1, .. })
+    ));
     Ok(())
 }
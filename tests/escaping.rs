@@ -1,3 +1,5 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
@@ -74,3 +76,54 @@ BabsCo, Inc.
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn escape_text_round_trip() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+NOTE:Line1\nLine2\; a\, b
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+
+    let card = vcards.remove(0);
+    let note = card.note.first().unwrap();
+    assert_eq!("Line1\nLine2; a, b", note.value);
+    assert!(card
+        .to_string()
+        .contains("NOTE:Line1\\nLine2\\; a\\, b\r\n"));
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn escape_note_unescaped() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+NOTE:a\,b\nc
+NOTE:C:\\new
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert_eq!("a,b\nc", card.note[0].value);
+    assert_eq!("C:\\new", card.note[1].value);
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn escape_no_percent_decoding() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+NOTE:50%25
+URL:https://example.com/a%20b
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert_eq!("50%25", card.note[0].value);
+    assert_eq!("https://example.com/a%20b", card.url[0].value.to_string());
+    assert!(card.to_string().contains("NOTE:50%25\r\n"));
+    assert_round_trip(&card)?;
+    Ok(())
+}
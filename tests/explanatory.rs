@@ -1,9 +1,16 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
-use vcard4::{parse, property::TextOrUriProperty};
+use vcard4::{
+    parse,
+    property::{ClientPidMap, Property, TextListProperty, TextOrUriProperty},
+    time::{Date, Month, PrimitiveDateTime, Time},
+    Error, Identity, Vcard,
+};
 
-use test_helpers::assert_round_trip;
+use test_helpers::{assert_round_trip, strip_position};
 
 #[test]
 fn explanatory_categories() -> Result<()> {
@@ -30,6 +37,39 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+fn explanatory_categories_merged() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+CATEGORIES:A,B
+CATEGORIES:b,C
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert_eq!(vec!["A", "B", "C"], card.categories());
+    Ok(())
+}
+
+#[test]
+fn explanatory_categories_escaped_comma() -> Result<()> {
+    let prop = TextListProperty::new_comma(vec![
+        String::from("a,b"),
+        String::from("c"),
+    ]);
+    assert_eq!("a\\,b,c", prop.to_string());
+
+    let mut card = Vcard::new(String::from("Jane Doe"));
+    card.categories.push(prop);
+    let output = card.to_string();
+    assert!(output.contains("CATEGORIES:a\\,b,c\r\n"));
+
+    let card = parse(&output)?.remove(0);
+    let prop = card.categories.first().unwrap();
+    assert_eq!(&vec!["a,b", "c"], &prop.value);
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
 #[test]
 fn explanatory_prod_id() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -65,6 +105,54 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+fn explanatory_rev_timestamp() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+REV:19951031T222710Z
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let expected = PrimitiveDateTime::new(
+        Date::from_calendar_date(1995, Month::October, 31)?,
+        Time::from_hms(22, 27, 10)?,
+    )
+    .assume_utc();
+    assert_eq!(expected, card.rev.as_ref().unwrap().value);
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+REV:1995-10-31
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::InvalidDateTime(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn explanatory_rev_timestamp_format() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+REV:20231231T235959Z
+X-SEEN;VALUE=timestamp:20231231T235959Z,20231231T235959-0500
+X-MET;VALUE=date-time:20231231T235959Z
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+
+    assert_eq!("20231231T235959Z", card.rev.as_ref().unwrap().to_string());
+    assert_eq!(
+        "20231231T235959Z,20231231T235959-0500",
+        card.extensions[0].value.to_string()
+    );
+    assert_eq!("20231231T235959Z", card.extensions[1].value.to_string());
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
 #[test]
 fn explanatory_sound() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -109,6 +197,36 @@ END:VCARD"#;
     Ok(())
 }
 
+#[cfg(feature = "uuid")]
+#[test]
+fn explanatory_uid_uuid() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+UID:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+UID:urn:uuid:not-a-uuid
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+FN:Mary Doe
+UID:https://example.com/mary
+END:VCARD"#;
+    let cards = parse(input)?;
+    assert_eq!(
+        Some(uuid::Uuid::parse_str(
+            "f81d4fae-7dec-11d0-a765-00a0c91e6bf6"
+        )?),
+        cards[0].uuid()
+    );
+    assert!(cards[1].uuid().is_none());
+    assert!(cards[2].uuid().is_none());
+    Ok(())
+}
+
 #[test]
 fn explanatory_note() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -155,6 +273,39 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+fn explanatory_client_pid_map_parts() -> Result<()> {
+    let map: ClientPidMap =
+        "2;urn:uuid:d89c9c7a-2e1b-4832-82de-7e992d95faa5".parse()?;
+    assert_eq!(2, map.source());
+    assert_eq!("urn", map.uri().scheme().as_str());
+    assert_eq!(
+        "urn:uuid:d89c9c7a-2e1b-4832-82de-7e992d95faa5",
+        map.uri().to_string()
+    );
+
+    for value in [
+        "2urn:uuid:d89c9c7a-2e1b-4832-82de-7e992d95faa5",
+        "0;urn:uuid:d89c9c7a-2e1b-4832-82de-7e992d95faa5",
+    ] {
+        assert!(matches!(
+            value.parse::<ClientPidMap>(),
+            Err(Error::InvalidClientPidMap(_))
+        ));
+    }
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+CLIENTPIDMAP:2
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::InvalidClientPidMap(_))
+    ));
+    Ok(())
+}
+
 #[test]
 fn explanatory_url() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -175,3 +326,106 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn explanatory_note_best_by_language() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+NOTE;LANGUAGE=en:Hello
+NOTE;LANGUAGE=fr:Bonjour
+NOTE:Hi
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let props = card
+        .note
+        .iter()
+        .map(|prop| prop as &dyn Property)
+        .collect::<Vec<_>>();
+
+    #[cfg(feature = "language-tags")]
+    let wanted = |tags: &[&str]| -> Vec<language_tags::LanguageTag> {
+        tags.iter().map(|tag| tag.parse().unwrap()).collect()
+    };
+    #[cfg(not(feature = "language-tags"))]
+    let wanted = |tags: &[&str]| -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    };
+
+    let best = card.best_by_language(&props, &wanted(&["fr-CA", "en"]));
+    assert_eq!("Bonjour", best.unwrap().to_string());
+    let best = card.best_by_language(&props, &wanted(&["de", "en-GB"]));
+    assert_eq!("Hello", best.unwrap().to_string());
+    let best = card.best_by_language(&props, &wanted(&["de"]));
+    assert_eq!("Hi", best.unwrap().to_string());
+    Ok(())
+}
+
+#[test]
+fn explanatory_uid_hash() -> Result<()> {
+    use std::{
+        collections::{hash_map::DefaultHasher, HashMap, HashSet},
+        hash::{Hash, Hasher},
+    };
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+UID:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+NOTE:Met at the conference
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+NOTE:Met at the conference
+UID:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+FN:Jane Doe
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+NICKNAME:Johnny
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+NICKNAME:Johnny
+FN:John Doe
+END:VCARD"#;
+    let cards = parse(input)?;
+    assert_eq!(4, cards.len());
+
+    let mut set = HashSet::new();
+    set.insert(cards[0].clone());
+    set.insert(cards[1].clone());
+    assert_eq!(1, set.len());
+
+    set.insert(cards[2].clone());
+    set.insert(cards[3].clone());
+    assert_eq!(2, set.len());
+
+    // Same UID with different content hashes the same but
+    // the cards are not equal
+    let mut updated = cards[0].clone();
+    updated.note[0].value = "Met at the workshop".to_owned();
+    let hash = |card: &Vcard| {
+        let mut hasher = DefaultHasher::new();
+        card.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&cards[0]), hash(&updated));
+    assert_ne!(cards[0], updated);
+
+    // Keyed by identity the cards with the same UID collapse
+    assert!(cards[0].same_identity(&updated));
+    assert!(!cards[0].same_identity(&cards[2]));
+    assert!(cards[2].same_identity(&cards[3]));
+    let mut identities = HashMap::new();
+    for card in cards.iter().chain(std::iter::once(&updated)) {
+        identities.insert(card.identity(), card.clone());
+    }
+    assert_eq!(2, identities.len());
+    let uid = Identity::Uid(
+        "urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6".to_owned(),
+    );
+    assert_eq!(Some(&updated), identities.get(&uid));
+    Ok(())
+}
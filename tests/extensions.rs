@@ -1,7 +1,9 @@
+#![allow(clippy::approx_constant, clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
-use test_helpers::{assert_language, assert_round_trip};
+use test_helpers::{assert_language, assert_round_trip, strip_position};
 use uriparse::uri::URI as Uri;
 use vcard4::{
     helper::{
@@ -10,7 +12,8 @@ use vcard4::{
     },
     parameter::{TypeParameter, ValueType},
     parse,
-    property::{AnyProperty, DateAndOrTime},
+    property::{AnyProperty, DateAndOrTime, ExtensionProperty, Property},
+    Error, VcardBuilder,
 };
 
 #[test]
@@ -280,6 +283,61 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+fn extension_boolean_tokens() -> Result<()> {
+    let card = |value: &str| {
+        parse(format!(
+            "BEGIN:VCARD\nVERSION:4.0\nFN:Jane Doe\n\
+            X-FOO;VALUE=boolean:{}\nEND:VCARD",
+            value
+        ))
+    };
+
+    for (value, expected) in [
+        ("true", true),
+        ("TRUE", true),
+        ("False", false),
+        ("fAlSe", false),
+    ] {
+        let card = card(value)?.remove(0);
+        let prop = card.extensions.first().unwrap();
+        assert_eq!(&AnyProperty::Boolean(expected), &prop.value);
+        let output = if expected { "TRUE" } else { "FALSE" };
+        assert_eq!(output, prop.value.to_string());
+        assert_round_trip(&card)?;
+    }
+
+    for value in ["yes", "1", ""] {
+        assert!(matches!(
+            strip_position(card(value)),
+            Err(Error::InvalidBoolean(_))
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn extension_integer_list() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+X-RANK;VALUE=integer:1,2,3
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let prop = card.extensions.first().unwrap();
+    assert_eq!(&AnyProperty::Integer(vec![1, 2, 3]), &prop.value);
+    assert_eq!(ValueType::Integer, prop.value.value_type());
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+X-RANK;VALUE=integer:1\,2
+END:VCARD"#;
+    assert!(parse(input).is_err());
+    Ok(())
+}
+
 #[test]
 fn extension_float() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -385,8 +443,8 @@ END:VCARD"#;
 
     assert_eq!(
         Some(vec![
-            ("X-QUX".to_owned(), vec!["baz".to_owned(), "zub".to_owned()]),
-            ("x-foo".to_owned(), vec!["bar".to_owned()])
+            ("x-foo".to_owned(), vec!["bar".to_owned()]),
+            ("X-QUX".to_owned(), vec!["baz".to_owned(), "zub".to_owned()])
         ]),
         prop.parameters.as_ref().unwrap().extensions
     );
@@ -413,7 +471,7 @@ END:VCARD"#;
     assert!(prop.group.is_none());
     assert_eq!("X-FOO", &prop.name);
     assert_eq!(
-        &vec![TypeParameter::Extension("baz".to_string())],
+        &vec![TypeParameter::Unknown("baz".to_string())],
         prop.parameters.as_ref().unwrap().types.as_ref().unwrap()
     );
 
@@ -422,3 +480,115 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn extension_unknown_properties() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nX-ABLabel;group=item1:Work\r\nitem2.SOCIALPROFILE;x-user=jdoe:https://example.com/jdoe\r\nSOCIALPROFILE;TYPE=twitter:https://twitter.com/jdoe\r\nEND:VCARD\r\n";
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let prop = card.extensions.first().unwrap();
+    assert_eq!("X-ABLabel", &prop.name);
+    assert!(prop.group.is_none());
+    assert_eq!(
+        Some(vec![("group".to_owned(), vec!["item1".to_owned()])]),
+        prop.parameters.as_ref().unwrap().extensions
+    );
+    assert_eq!(&AnyProperty::Text("Work".to_string()), &prop.value);
+
+    let prop = card.extensions.get(1).unwrap();
+    assert_eq!("SOCIALPROFILE", &prop.name);
+    assert_eq!(Some("item2"), prop.group.as_deref());
+    assert_eq!(
+        &AnyProperty::Text("https://example.com/jdoe".to_string()),
+        &prop.value
+    );
+
+    let prop = card.extensions.get(2).unwrap();
+    assert_eq!("SOCIALPROFILE", &prop.name);
+    assert_eq!(
+        &vec![TypeParameter::Unknown("twitter".to_string())],
+        prop.parameters.as_ref().unwrap().types.as_ref().unwrap()
+    );
+
+    assert_eq!(input, card.to_string());
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn extension_display_uri() -> Result<()> {
+    let mut card = VcardBuilder::new("Jane Doe".to_owned()).finish();
+    card.extensions.push(ExtensionProperty {
+        name: "X-HOMEPAGE".to_owned(),
+        group: Some("item1".to_owned()),
+        value: AnyProperty::Uri(
+            Uri::try_from("https://example.com/~jane")?.into_owned(),
+        ),
+        parameters: None,
+    });
+    card.extensions[0]
+        .parameters
+        .get_or_insert_with(Default::default)
+        .value = Some(ValueType::Uri);
+
+    let prop = card.extensions.first().unwrap();
+    assert_eq!(Some(&"item1".to_owned()), prop.group());
+    assert_eq!("https://example.com/~jane", prop.to_string());
+
+    let encoded = card.to_string();
+    assert!(encoded.contains(
+        "item1.X-HOMEPAGE;VALUE=uri:https://example.com/~jane\r\n"
+    ));
+
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn extension_any_property_display() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+X-TEXT:a\,b\;c\nd
+X-INTEGER;VALUE=integer:1,-2
+X-FLOAT;VALUE=float:1.5
+X-TRUE;VALUE=boolean:true
+X-FALSE;VALUE=boolean:False
+X-DATE;VALUE=date:19850412
+X-TIME;VALUE=time:102200Z
+X-DATE-TIME;VALUE=date-time:19961022T140000+0500
+X-TIMESTAMP;VALUE=timestamp:20231231T235959Z
+X-URI;VALUE=uri:http://example.com/foo
+X-OFFSET;VALUE=utc-offset:-0500
+X-LANG;VALUE=language-tag:fr
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+
+    let values = card
+        .extensions
+        .iter()
+        .map(|prop| prop.value.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "a\\,b\\;c\\nd",
+            "1,-2",
+            "1.5",
+            "TRUE",
+            "FALSE",
+            "19850412",
+            "102200Z",
+            "19961022T140000+0500",
+            "20231231T235959Z",
+            "http://example.com/foo",
+            "-0500",
+            "fr",
+        ],
+        values
+    );
+
+    assert_round_trip(&card)?;
+    Ok(())
+}
@@ -1,8 +1,11 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
+use std::io::Cursor;
 use test_helpers::assert_round_trip;
-use vcard4::parse;
+use vcard4::{parse, Vcard, VcardBuilder};
 
 #[test]
 fn parse_multi_byte() -> Result<()> {
@@ -55,3 +58,71 @@ fn parse_folded_tab() -> Result<()> {
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn fold_long_value() -> Result<()> {
+    let note = "Grüße aus Köln! ".repeat(16);
+    assert!(note.len() >= 300);
+
+    let card = VcardBuilder::new("Jane Doe".to_owned())
+        .note(note.clone())
+        .finish();
+
+    let encoded = card.to_string();
+    for line in encoded.split("\r\n") {
+        assert!(line.len() <= 75);
+    }
+    assert!(encoded.contains("\r\n "));
+
+    let mut vcards = parse(&encoded)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+    let prop = card.note.first().unwrap();
+    assert_eq!(note.as_bytes(), prop.value.as_bytes());
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn parse_folded_note() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nNOTE:This is a l\r\n ong note that\r\n\t spans\r\n \r\n  several lines\\, wit\r\n h escapes\r\nEND:VCARD";
+
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+
+    let card = vcards.remove(0);
+    let note = card.note.first().unwrap();
+    assert_eq!(
+        "This is a long note that spans several lines, with escapes",
+        note.value
+    );
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn parse_line_endings() -> Result<()> {
+    let lf =
+        "BEGIN:VCARD\nVERSION:4.0\nFN:Jane\n  Doe\nNOTE:Note\nEND:VCARD\n";
+    let crlf = lf.replace('\n', "\r\n");
+    let cr = lf.replace('\n', "\r");
+    let mixed =
+        "BEGIN:VCARD\r\nVERSION:4.0\rFN:Jane\n  Doe\r\nNOTE:Note\rEND:VCARD";
+
+    let expected = parse(&crlf)?.remove(0);
+    assert_eq!("Jane Doe", expected.formatted_name[0].value);
+    for input in [lf, &cr, mixed] {
+        let card = parse(input)?.remove(0);
+        assert_eq!(expected, card);
+        assert_eq!(
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nNOTE:Note\r\nEND:VCARD\r\n",
+            card.to_string()
+        );
+
+        let cards = Vcard::from_reader(Cursor::new(input))
+            .collect::<vcard4::Result<Vec<_>>>()?;
+        assert_eq!(expected, cards[0]);
+        assert_eq!(expected, Vcard::parse_many(input)?.remove(0));
+    }
+    Ok(())
+}
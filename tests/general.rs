@@ -1,8 +1,10 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
 use test_helpers::assert_round_trip;
-use vcard4::{parse, property::*};
+use vcard4::{parameter::TelephoneType, parse, property::*, Error};
 
 // General
 
@@ -75,6 +77,39 @@ END:VCARD"#;
         assert_eq!(Kind::Org, card.kind.as_ref().unwrap().value);
         assert_round_trip(&card)?;
         */
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+KIND:x-robot
+FN:Robbie
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let kind = &card.kind.as_ref().unwrap().value;
+    assert_eq!(&Kind::Extension(String::from("x-robot")), kind);
+    assert_eq!("x-robot", kind.to_string());
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+KIND:device
+FN:Printer
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let kind = &card.kind.as_ref().unwrap().value;
+    assert_eq!(&Kind::Iana(String::from("device")), kind);
+    assert_eq!("device", kind.to_string());
+    assert_round_trip(&card)?;
+
+    assert!(matches!(
+        "not a kind".parse::<Kind>(),
+        Err(Error::UnknownKind(_))
+    ));
     Ok(())
 }
 
@@ -92,5 +127,224 @@ END:VCARD"#;
     let xml = card.xml.get(0).unwrap();
     assert_eq!("<root></root>", &xml.value);
     assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+XML:<foo a="1\,2">bar;baz\nqux</foo>
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let xml = card.xml.first().unwrap();
+    assert_eq!("<foo a=\"1,2\">bar;baz\nqux</foo>", &xml.value);
+    assert!(card
+        .to_string()
+        .contains("\r\nXML:<foo a=\"1\\,2\">bar\\;baz\\nqux</foo>\r\n"));
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn general_property_order() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nADR:;;123 Main Street;Any Town;CA;91921-1234;U.S.A.\r\nX-FOO:bar\r\nTEL:+1-555-555-5555\r\nEMAIL:jane@example.com\r\nFN:Jane Doe\r\nTEL:+1-555-555-5556\r\nEND:VCARD\r\n";
+    let card = parse(input)?.remove(0);
+    assert_eq!(input, card.to_string());
+    assert_round_trip(&card)?;
+
+    // Properties added after parsing follow in the default order
+    let mut card = card;
+    card.note.push("Note".to_string().into());
+    assert!(card
+        .to_string()
+        .ends_with("TEL:+1-555-555-5556\r\nNOTE:Note\r\nEND:VCARD\r\n"));
+    Ok(())
+}
+
+#[test]
+fn general_merge() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+UID:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+EMAIL:jane@example.com
+EMAIL:jdoe@example.org
+END:VCARD"#;
+    let mut card = parse(input)?.remove(0);
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;PREF=1:Jane Q. Doe
+UID:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+EMAIL:jdoe@example.org
+EMAIL:jane.doe@example.net
+TEL:+1-555-555-5555
+END:VCARD"#;
+    let other = parse(input)?.remove(0);
+
+    card.merge(other)?;
+
+    let emails = card
+        .email
+        .iter()
+        .map(|prop| &prop.value[..])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "jane@example.com",
+            "jdoe@example.org",
+            "jane.doe@example.net"
+        ],
+        emails
+    );
+    assert_eq!(1, card.tel.len());
+    assert_eq!(1, card.formatted_name.len());
+    assert_eq!("Jane Q. Doe", card.formatted_name[0].value);
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+UID:urn:uuid:a81d4fae-7dec-11d0-a765-00a0c91e6bf6
+END:VCARD"#;
+    let other = parse(input)?.remove(0);
+    assert!(matches!(card.merge(other), Err(Error::UidMismatch)));
+    Ok(())
+}
+
+#[test]
+fn general_diff() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL:jane@example.com
+TEL:+1-555-555-5555
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL:jane@example.com
+TEL:+1-555-555-5555
+work.TEL;TYPE=work:+1-555-555-1234
+END:VCARD"#;
+    let other = parse(input)?.remove(0);
+
+    let diff = card.diff(&other);
+    assert!(diff.removed.is_empty());
+    assert!(diff.modified.is_empty());
+    assert_eq!(1, diff.added.len());
+
+    let change = &diff.added[0];
+    assert_eq!(PropertyKind::Telephone, change.kind);
+    assert_eq!("TEL", change.name);
+    assert_eq!(Some("work"), change.group.as_deref());
+    assert_eq!("+1-555-555-1234", change.value);
+
+    let reverse = other.diff(&card);
+    assert_eq!(diff.added, reverse.removed);
+    assert!(card.diff(&card).is_empty());
+    Ok(())
+}
+
+#[test]
+fn general_case_insensitive_names() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+version:4.0
+fn:Jane Doe
+Adr;Type=home:;;1 Main St;;;;
+tel;type=WORK,Cell;Value=uri:tel:+1-555-555-5555
+Email;PREF=1:jane@example.com
+tz:Europe/Paris
+item1.x-foo;Type=Home:bar
+end:vcard"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    assert_eq!("Jane Doe", card.formatted_name[0].value);
+    assert_eq!(1, card.address.len());
+    assert_eq!(1, card.email.len());
+    assert_eq!(1, card.timezone.len());
+    assert_eq!(
+        vec![TelephoneType::Cell],
+        card.telephones()[0].telephone_types()
+    );
+
+    // type names and values are case-insensitive
+    let types = |prop: &dyn Property| {
+        prop.parameters().and_then(|params| params.types.clone())
+    };
+    assert_eq!(types(&card.address[0]), types(&card.extensions[0]));
+
+    // extension names retain their case
+    assert_eq!("x-foo", card.extensions[0].name);
+    assert_eq!(Some(&"item1".to_owned()), card.extensions[0].group());
+
+    let encoded = card.to_string();
+    assert!(encoded.contains("\r\nFN:Jane Doe\r\n"));
+    assert!(encoded.contains("\r\nADR;TYPE=home:;;1 Main St;;;;\r\n"));
+    assert!(encoded.contains("\r\nitem1.x-foo;TYPE=home:bar\r\n"));
+
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn general_canonicalize() -> Result<()> {
+    let first = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+TEL;TYPE=work,voice;X-A=1;X-B=2:tel:+1-555-555-5555
+NOTE:Likes\, dislikes
+EMAIL:jane@example.com
+END:VCARD"#;
+    let second = r#"BEGIN:VCARD
+VERSION:4.0
+EMAIL:jane@example.com
+note:Likes\, dislikes
+tel;x-b=2;TYPE=voice,work;X-A=1:tel:+1-555-555-5555
+FN:Jane Doe
+END:VCARD"#;
+    let first = parse(first)?.remove(0);
+    let second = parse(second)?.remove(0);
+    assert_ne!(first.to_string(), second.to_string());
+
+    let canonical = first.canonicalize();
+    assert_eq!(canonical, second.canonicalize());
+    assert_eq!(
+        "BEGIN:VCARD\r\n\
+        VERSION:4.0\r\n\
+        EMAIL:jane@example.com\r\n\
+        FN:Jane Doe\r\n\
+        NOTE:Likes\\, dislikes\r\n\
+        TEL;TYPE=voice,work;X-A=1;X-B=2:tel:+1-555-555-5555\r\n\
+        END:VCARD\r\n",
+        canonical
+    );
+    Ok(())
+}
+
+#[test]
+fn general_blank_lines_and_trailing_whitespace() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\n\r\nFN:Jane Doe \r\nEMAIL:jane@example.com  \r\nURL:https://example.com/\t\r\n\r\nEND:VCARD\r\n";
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+    assert_eq!("Jane Doe ", card.formatted_name[0].value);
+    assert_eq!("jane@example.com", card.email[0].value);
+    assert_eq!("https://example.com/", card.url[0].value.to_string());
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn general_display() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nEMAIL;TYPE=work:jane@example.com\r\nNOTE:This is a long note that is folded because it exceeds the limit of sev\r\n enty five octets.\r\nEND:VCARD\r\n";
+    let card = parse(input)?.remove(0);
+    assert_eq!(input, card.to_string());
+
+    // Bare line breaks are normalized to CRLF
+    let card = parse(input.replace("\r\n", "\n"))?.remove(0);
+    assert_eq!(input, card.to_string());
     Ok(())
 }
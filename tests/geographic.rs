@@ -1,8 +1,10 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
 use test_helpers::assert_round_trip;
-use vcard4::{parse, property::*};
+use vcard4::{parse, property::*, Error};
 
 // Geographic Properties
 
@@ -89,3 +91,39 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn geographic_geo_coordinates() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Mr. John Q. Public\, Esq.
+GEO:geo:37.386,-122.0838;u=35
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+
+    let card = vcards.remove(0);
+    let coords = card.geo.first().unwrap().coordinates()?;
+    assert_eq!((37.386, -122.0838), coords.lat_lon());
+    assert_eq!(None, coords.altitude);
+    assert_eq!(Some(35.0), coords.uncertainty);
+    assert_round_trip(&card)?;
+
+    let coords: Coordinates = "geo:40.685922,-111.853206,1321".parse()?;
+    assert_eq!(Some(1321.0), coords.altitude);
+    assert_eq!("geo:40.685922,-111.853206,1321", coords.to_string());
+
+    assert!(matches!(
+        "geo:91,0".parse::<Coordinates>(),
+        Err(Error::InvalidGeoUri(_))
+    ));
+    assert!(matches!(
+        "geo:37.386".parse::<Coordinates>(),
+        Err(Error::InvalidGeoUri(_))
+    ));
+    assert!(matches!(
+        "https://example.com".parse::<Coordinates>(),
+        Err(Error::InvalidGeoUri(_))
+    ));
+    Ok(())
+}
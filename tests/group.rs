@@ -1,8 +1,10 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
-use test_helpers::assert_round_trip;
-use vcard4::parse;
+use test_helpers::{assert_round_trip, strip_position};
+use vcard4::{parse, Error};
 
 #[test]
 fn group() -> Result<()> {
@@ -26,3 +28,71 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn group_extension_label() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nitem1.EMAIL:jane@example.com\r\nitem1.X-ABLabel:Work\r\nEND:VCARD\r\n";
+
+    let card = parse(input)?.remove(0);
+    assert_eq!(Some("item1".to_string()), card.email[0].group);
+    let label = card.extensions.first().unwrap();
+    assert_eq!(Some("item1".to_string()), label.group);
+    assert_eq!("X-ABLabel", label.name);
+
+    assert_eq!(input, card.to_string());
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn group_invalid_name() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+item-1.EMAIL:jane@example.com
+END:VCARD"#;
+    let mut card = parse(input)?.remove(0);
+    assert_eq!(Some("item-1".to_string()), card.email[0].group);
+    assert!(card.validate_all().is_empty());
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+itm_1.EMAIL:jane@example.com
+END:VCARD"#;
+    let result = strip_position(parse(input));
+    assert!(
+        matches!(result, Err(Error::InvalidGroup(group)) if group == "itm_1")
+    );
+
+    card.email[0].group = Some("item.1".to_owned());
+    let errors = card.validate_all();
+    assert!(
+        matches!(&errors[..], [Error::InvalidGroup(group)] if group == "item.1")
+    );
+    Ok(())
+}
+
+#[test]
+fn group_members() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+item1.ADR:;;123 Main Street;Any Town;CA;91921;U.S.A.
+item1.X-ABLabel:Holiday home
+item2.X-ABLabel:Unused
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+
+    let members = card.group("item1");
+    assert_eq!(2, members.len());
+    assert_eq!(
+        ";;123 Main Street;Any Town;CA;91921;U.S.A.",
+        members[0].to_string()
+    );
+    assert_eq!("Holiday home", members[1].to_string());
+
+    assert_eq!(2, card.group("ITEM1").len());
+    assert!(card.group("item3").is_empty());
+    Ok(())
+}
@@ -1,8 +1,11 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
+use std::collections::HashSet;
 use test_helpers::assert_round_trip;
-use vcard4::{parse, property::*};
+use vcard4::{parse, property::*, Error, VcardBuilder};
 
 #[test]
 fn identification_fn() -> Result<()> {
@@ -31,8 +34,12 @@ END:VCARD"#;
     assert_eq!(1, vcards.len());
 
     let card = vcards.remove(0);
-    let name = card.name.as_ref().unwrap();
-    assert_eq!(vec!["Public", "John", "Quinlan", "Mr.", "Esq."], name.value);
+    let name = &card.name.as_ref().unwrap().value;
+    assert_eq!(vec!["Public"], name.family_names);
+    assert_eq!(vec!["John"], name.given_names);
+    assert_eq!(vec!["Quinlan"], name.additional_names);
+    assert_eq!(vec!["Mr."], name.honorific_prefixes);
+    assert_eq!(vec!["Esq."], name.honorific_suffixes);
     assert_round_trip(&card)?;
     Ok(())
 }
@@ -48,8 +55,33 @@ END:VCARD"#;
     assert_eq!(1, vcards.len());
 
     let card = vcards.remove(0);
-    let name = card.name.as_ref().unwrap();
-    assert_eq!(vec!["Public", "John", "Quinlan", "Mr.", "Esq."], name.value);
+    let name = &card.name.as_ref().unwrap().value;
+    assert_eq!(vec!["Public"], name.family_names);
+    assert_eq!(vec!["John"], name.given_names);
+    assert_eq!(vec!["Quinlan"], name.additional_names);
+    assert_eq!(vec!["Mr."], name.honorific_prefixes);
+    assert_eq!(vec!["Esq."], name.honorific_suffixes);
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn identification_nickname_list() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jim Smith
+NICKNAME:Jim,Jimmy
+NICKNAME:J\,S
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+
+    let card = vcards.remove(0);
+    assert_eq!(vec!["Jim", "Jimmy"], card.nickname[0].value);
+    assert_eq!(vec!["J,S"], card.nickname[1].value);
+    let encoded = card.to_string();
+    assert!(encoded.contains("NICKNAME:Jim,Jimmy\r\n"));
+    assert!(encoded.contains("NICKNAME:J\\,S\r\n"));
     assert_round_trip(&card)?;
     Ok(())
 }
@@ -160,7 +192,35 @@ END:VCARD"#;
 
     let card = vcards.remove(0);
     let anniversary = card.anniversary.as_ref().unwrap();
-    assert_eq!("20090808T143000-0500", &anniversary.to_string(),);
+    assert_eq!("20090808T1430-0500", &anniversary.to_string(),);
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Mr. John Q. Public\, Esq.
+ANNIVERSARY;VALUE=text:circa 1800
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+
+    let card = vcards.remove(0);
+    let anniversary = card.anniversary.as_ref().unwrap();
+    assert!(matches!(anniversary, DateTimeOrTextProperty::Text(_)));
+    assert_eq!("circa 1800", &anniversary.to_string());
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Mr. John Q. Public\, Esq.
+ANNIVERSARY:--0314
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+
+    let card = vcards.remove(0);
+    let anniversary = card.anniversary.as_ref().unwrap();
+    assert!(matches!(anniversary, DateTimeOrTextProperty::DateTime(_)));
+    assert_eq!("--0314", &anniversary.to_string());
     assert_round_trip(&card)?;
 
     Ok(())
@@ -284,3 +344,163 @@ END:VCARD"#;
 
     Ok(())
 }
+
+#[test]
+fn identification_n_components() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Simon Perreault
+N:Perreault;Simon;;;ing. jr,M.Sc.
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let name = &card.name.as_ref().unwrap().value;
+    assert_eq!(vec!["Perreault"], name.family_names);
+    assert!(name.additional_names.is_empty());
+    assert!(name.honorific_prefixes.is_empty());
+    assert_eq!(vec!["ing. jr", "M.Sc."], name.honorific_suffixes);
+    assert_eq!("Perreault;Simon;;;ing. jr,M.Sc.", name.to_string());
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+N:Doe\; Smith;Jane\, Mary;;;
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let name = &card.name.as_ref().unwrap().value;
+    assert_eq!(vec!["Doe; Smith"], name.family_names);
+    assert_eq!(vec!["Jane, Mary"], name.given_names);
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+N:;;;;
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let name = &card.name.as_ref().unwrap().value;
+    assert_eq!(&StructuredName::default(), name);
+    assert_round_trip(&card)?;
+
+    Ok(())
+}
+
+#[test]
+fn identification_photo_data_uri() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+PHOTO:data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==
+PHOTO:http://www.example.com/pub/photos/jqpublic.gif
+LOGO:data:,Hello%2C%20World
+SOUND:data:audio/basic;base64,!!!
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let photo = card.photo[0].as_data_uri()?.unwrap();
+    assert_eq!("image/png", photo.media_type.as_ref().unwrap().to_string());
+    assert_eq!(&[0x89, b'P', b'N', b'G'], &photo.data[..4]);
+    assert!(card.photo[1].as_data_uri()?.is_none());
+
+    let logo = card.logo[0].as_data_uri()?.unwrap();
+    assert!(logo.media_type.is_none());
+    assert_eq!(b"Hello, World", &logo.data[..]);
+
+    assert!(matches!(card.sound[0].as_data_uri(), Err(Error::Base64(_))));
+    Ok(())
+}
+
+#[test]
+fn identification_photo_from_bytes() -> Result<()> {
+    let prop = UriProperty::from_bytes("image/png", &[0x89, 0x50, 0x4e])?;
+    assert_eq!("data:image/png;base64,iVBO", prop.value.to_string());
+
+    let mut card = VcardBuilder::new("Jane Doe".to_owned()).finish();
+    card.photo.push(TextOrUriProperty::Uri(prop));
+    let card = parse(card.to_string())?.remove(0);
+
+    let photo = card.photo[0].as_data_uri()?.unwrap();
+    assert_eq!("image/png", photo.media_type.as_ref().unwrap().to_string());
+    assert_eq!(vec![0x89, 0x50, 0x4e], photo.data);
+    Ok(())
+}
+
+#[test]
+fn identification_gender_order() -> Result<()> {
+    let mut values = vec![
+        Sex::Unknown,
+        Sex::Female,
+        Sex::None,
+        Sex::NotApplicable,
+        Sex::Male,
+        Sex::Other,
+    ];
+    values.sort();
+    assert_eq!(
+        vec![
+            Sex::None,
+            Sex::Male,
+            Sex::Female,
+            Sex::Other,
+            Sex::NotApplicable,
+            Sex::Unknown,
+        ],
+        values
+    );
+
+    let mut kinds = vec![Kind::Location, Kind::Individual, Kind::Org];
+    kinds.sort();
+    assert_eq!(vec![Kind::Individual, Kind::Org, Kind::Location], kinds);
+
+    let genders: HashSet<Gender> = ["M", "F", "M"]
+        .iter()
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()?;
+    assert_eq!(2, genders.len());
+    Ok(())
+}
+
+#[test]
+fn identification_gender_default() -> Result<()> {
+    let gender = Gender::default();
+    assert_eq!(Sex::None, gender.sex);
+    assert!(gender.identity.is_none());
+    assert_eq!("", gender.to_string());
+    assert_eq!(gender, "".parse()?);
+    assert_eq!(Sex::None, Sex::default());
+    Ok(())
+}
+
+#[test]
+fn identification_gender_escaped_identity() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+GENDER:O;non-binary\; fluid\, mostly
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let gender = &card.gender.as_ref().unwrap().value;
+    assert_eq!(Sex::Other, gender.sex);
+    assert_eq!(
+        Some("non-binary; fluid, mostly"),
+        gender.identity.as_deref()
+    );
+    assert_eq!(r"O;non-binary\; fluid\, mostly", gender.to_string());
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn identification_bday_non_ascii() -> Result<()> {
+    for value in ["1234é1", "--aé1", "é"] {
+        let input = format!(
+            "BEGIN:VCARD\nVERSION:4.0\nFN:Jane Doe\nBDAY:{}\nEND:VCARD",
+            value
+        );
+        assert!(parse(input).is_err());
+        assert!(value.parse::<DateAndOrTime>().is_err());
+    }
+    Ok(())
+}
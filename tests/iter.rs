@@ -1,5 +1,8 @@
+#![allow(clippy::redundant_pattern_matching)]
+
 use anyhow::Result;
-use vcard4::iter;
+use std::io::Cursor;
+use vcard4::{iter, parse, Error, LexError, Vcard};
 
 #[test]
 fn iter_one() -> Result<()> {
@@ -46,3 +49,139 @@ fn iter_error_expected() -> Result<()> {
     assert!(matches!(it.next(), None));
     Ok(())
 }
+
+#[test]
+fn parse_many() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n\r\nbegin:vcard\r\nVERSION:4.0\r\nFN:John\r\n  Doe\r\nend:vcard\r\nBEGIN:VCARD\nVERSION:3.0\nFN:Mock Company\nN:;;;;\nEND:VCARD\n";
+    let cards = Vcard::parse_many(input)?;
+    assert_eq!(3, cards.len());
+    assert_eq!("Jane Doe", cards[0].formatted_name[0].value);
+    assert_eq!("John Doe", cards[1].formatted_name[0].value);
+    assert_eq!("Mock Company", cards[2].formatted_name[0].value);
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+"#;
+    let result = Vcard::parse_many(input);
+    assert!(matches!(result, Err(Error::UnterminatedCard(1))));
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+END:VCARD
+garbage
+BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+END:VCARD"#;
+    let result = Vcard::parse_many(input);
+    assert!(matches!(result, Err(Error::UnexpectedContent(1))));
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+BDAY:--0203
+BDAY:--0203
+END:VCARD"#;
+    let result = Vcard::parse_many(input);
+    assert!(matches!(
+        result,
+        Err(Error::InvalidCard { index: 1, source })
+            if matches!(
+                &*source,
+                Error::At { line: 8, column: 1, source }
+                    if matches!(**source, Error::OnlyOnce(_))
+            )
+    ));
+    Ok(())
+}
+
+#[test]
+fn from_reader() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane\r\n  Doe\r\nEND:VCARD\r\n\r\nBEGIN:VCARD\r\nVERSION:4.0\r\nFN:John Doe\r\nEND:VCARD\r\n";
+    let cards = Vcard::from_reader(Cursor::new(input))
+        .collect::<vcard4::Result<Vec<_>>>()?;
+    assert_eq!(2, cards.len());
+    assert_eq!("Jane Doe", cards[0].formatted_name[0].value);
+    assert_eq!("John Doe", cards[1].formatted_name[0].value);
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+END:VCARD
+BEGIN:VCARD
+VERSION:4.0
+"#;
+    let mut it = Vcard::from_reader(Cursor::new(input));
+    assert!(matches!(
+        it.next(),
+        Some(Err(Error::InvalidCard { index: 0, .. }))
+    ));
+    assert!(matches!(it.next(), Some(Ok(_))));
+    assert!(matches!(it.next(), Some(Err(Error::UnterminatedCard(2)))));
+    assert!(it.next().is_none());
+
+    let input =
+        b"BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane \xFF\r\nEND:VCARD\r\n";
+    let mut it = Vcard::from_reader(Cursor::new(&input[..]));
+    assert!(matches!(
+        it.next(),
+        Some(Err(Error::LexError(LexError::InvalidUtf8(34))))
+    ));
+    assert!(it.next().is_none());
+    Ok(())
+}
+
+#[test]
+fn byte_order_mark() -> Result<()> {
+    let input = "\u{FEFF}BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n\u{FEFF}BEGIN:VCARD\r\nVERSION:4.0\r\nFN:John Doe\r\nEND:VCARD\r\n";
+
+    let cards = parse(input)?;
+    assert_eq!(2, cards.len());
+    assert_eq!("Jane Doe", cards[0].formatted_name[0].value);
+    assert_eq!("John Doe", cards[1].formatted_name[0].value);
+
+    assert_eq!(
+        2,
+        iter(input, true).collect::<vcard4::Result<Vec<_>>>()?.len()
+    );
+    assert_eq!(2, Vcard::parse_many(input)?.len());
+    let cards = Vcard::from_reader(Cursor::new(input))
+        .collect::<vcard4::Result<Vec<_>>>()?;
+    assert_eq!(2, cards.len());
+
+    // Byte order mark before the end of a vCard
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane\r\n  Doe\r\n\u{FEFF}END:VCARD\r\n\u{FEFF}BEGIN:VCARD\r\nVERSION:4.0\r\nFN:John Doe\r\nEND:VCARD\r\n";
+    let cards = Vcard::parse_many(input)?;
+    assert_eq!(2, cards.len());
+    assert_eq!("Jane Doe", cards[0].formatted_name[0].value);
+    assert_eq!("John Doe", cards[1].formatted_name[0].value);
+    let cards = Vcard::from_reader(Cursor::new(input))
+        .collect::<vcard4::Result<Vec<_>>>()?;
+    assert_eq!(2, cards.len());
+    assert_eq!(2, parse(input)?.len());
+
+    // Line numbers account for folds and byte order marks
+    let input = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane\r\n  Doe\r\n\u{FEFF}END:VCARD\r\n\u{FEFF}BEGIN:VCARD\r\nVERSION:4.0\r\nBDAY:--0203\r\nBDAY:--0203\r\n\u{FEFF}END:VCARD\r\n";
+    assert!(matches!(
+        Vcard::parse_many(input),
+        Err(Error::InvalidCard { index: 1, source })
+            if matches!(
+                &*source,
+                Error::At { line: 9, column: 1, source }
+                    if matches!(**source, Error::OnlyOnce(_))
+            )
+    ));
+    Ok(())
+}
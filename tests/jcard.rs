@@ -0,0 +1,174 @@
+#![cfg(feature = "serde")]
+
+use anyhow::Result;
+use serde_json::json;
+use vcard4::{parse, Error, Vcard};
+
+#[test]
+fn jcard_rfc_example() -> Result<()> {
+    // https://www.rfc-editor.org/rfc/rfc7095#section-3.1
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:John Doe
+GENDER:M
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let expected = json!([
+        "vcard",
+        [
+            ["version", {}, "text", "4.0"],
+            ["fn", {}, "text", "John Doe"],
+            ["gender", {}, "text", "M"]
+        ]
+    ]);
+    assert_eq!(expected, card.to_jcard());
+    Ok(())
+}
+
+#[test]
+fn jcard_structured() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Simon Perreault
+N:Perreault;Simon;;;ing. jr
+BDAY:19850412
+item1.ADR;TYPE=work:;Suite D2-630;2875 Laurier;Quebec;QC;G1V 2M2;Canada
+TEL;VALUE=uri;TYPE="work,voice";PREF=1:tel:+1-418-656-9254;ext=102
+CATEGORIES:computers,cameras
+TZ;VALUE=utc-offset:-0500
+REV:20090808T143000Z
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let expected = json!(["vcard",
+      [
+        ["version", {}, "text", "4.0"],
+        ["fn", {}, "text", "Simon Perreault"],
+        ["n", {}, "text", ["Perreault", "Simon", "", "", "ing. jr"]],
+        ["bday", {}, "date-and-or-time", "1985-04-12"],
+        ["adr", {"type": "work", "group": "item1"}, "text",
+          ["", "Suite D2-630", "2875 Laurier", "Quebec", "QC",
+           "G1V 2M2", "Canada"]],
+        ["tel", {"type": ["work", "voice"], "pref": "1"}, "uri",
+          "tel:+1-418-656-9254;ext=102"],
+        ["tz", {}, "utc-offset", "-05:00"],
+        ["categories", {}, "text", "computers", "cameras"],
+        ["rev", {}, "timestamp", "2009-08-08T14:30:00Z"]
+      ]
+    ]);
+    assert_eq!(expected, card.to_jcard());
+    Ok(())
+}
+
+#[test]
+fn jcard_round_trip() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+KIND:individual
+FN:Simon Perreault
+N:Perreault;Simon;;;ing. jr
+NICKNAME:Si
+BDAY:--0203
+ANNIVERSARY:20090808T1430-0500
+GENDER:M;Male\, mostly
+LANG;PREF=1:fr
+LANG;PREF=2:en
+ORG;TYPE=work:Viagenie
+item1.ADR;TYPE=work:;Suite D2-630;2875 Laurier;Quebec;QC;G1V 2M2;Canada
+TEL;VALUE=uri;TYPE="work,voice";PREF=1:tel:+1-418-656-9254;ext=102
+EMAIL;TYPE=work:simon.perreault@viagenie.ca
+GEO;TYPE=work:geo:46.772673,-71.282945
+KEY;TYPE=work:http://www.viagenie.ca/simon.perreault/simon.asc
+TZ;VALUE=utc-offset:-0500
+URL;TYPE=home:http://nomis80.org
+CATEGORIES:computers,cameras
+NOTE:Line one\nLine two
+REV:20090808T143000Z
+X-FOO;VALUE=integer:1,2
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let jcard = card.to_jcard();
+    let decoded = Vcard::from_jcard(&jcard)?;
+    assert_eq!(card, decoded);
+    Ok(())
+}
+
+#[test]
+fn jcard_invalid() -> Result<()> {
+    let value = json!(["vcalendar", []]);
+    assert!(matches!(
+        Vcard::from_jcard(&value),
+        Err(Error::InvalidJCard(_))
+    ));
+
+    let value = json!(["vcard", [["fn", {}, "text"]]]);
+    assert!(matches!(
+        Vcard::from_jcard(&value),
+        Err(Error::InvalidJCard(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn jcard_invalid_names() -> Result<()> {
+    let invalid = [
+        json!(["vcard", [["note:x\r\nuid", {}, "text", "y"]]]),
+        json!(["vcard", [["end", {}, "text", "VCARD"]]]),
+        json!(["vcard", [["fn", {"group": "a:b"}, "text", "Jane"]]]),
+        json!(["vcard", [["fn", {"x-a:b": "c"}, "text", "Jane"]]]),
+        json!(["vcard", [["url", {}, "uri", "http://a\r\nUID:b"]]]),
+    ];
+    for value in invalid {
+        assert!(matches!(
+            Vcard::from_jcard(&value),
+            Err(Error::InvalidJCard(_))
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn jcard_non_ascii_date() -> Result<()> {
+    let value = json!([
+        "vcard",
+        [["fn", {}, "text", "Jane"], ["bday", {}, "date", "123é-1"]]
+    ]);
+    assert!(Vcard::from_jcard(&value).is_err());
+    Ok(())
+}
+
+#[test]
+fn jcard_parameter_values() -> Result<()> {
+    let value = json!([
+        "vcard",
+        [
+            ["fn", {}, "text", "Jane Doe"],
+            [
+                "adr",
+                {"label": "a\nb \"c\"; d", "type": ["home", "work"]},
+                "text",
+                ["", "", "Main St", "", "", "", ""]
+            ],
+            ["note", {"x-tag": "a^b,c:d"}, "text", "Note"],
+            ["fn", {"tz": "-0500"}, "text", "Janey"]
+        ]
+    ]);
+    let card = Vcard::from_jcard(&value)?;
+    assert!(card.uid.is_none());
+
+    let params = card.address[0].parameters.as_ref().unwrap();
+    assert_eq!(Some("a\nb \"c\"; d"), params.label.as_deref());
+    assert_eq!(2, params.types.as_ref().unwrap().len());
+
+    let params = card.note[0].parameters.as_ref().unwrap();
+    assert_eq!(
+        &vec![("X-TAG".to_owned(), vec!["a^b,c:d".to_owned()])],
+        params.extensions.as_ref().unwrap()
+    );
+    assert!(card.formatted_name[1]
+        .parameters
+        .as_ref()
+        .unwrap()
+        .timezone
+        .is_some());
+    Ok(())
+}
@@ -1,8 +1,13 @@
 mod test_helpers;
 
 use anyhow::Result;
-use test_helpers::assert_round_trip;
-use vcard4::{parse_loose, property::Kind};
+use test_helpers::{assert_round_trip, strip_position};
+use vcard4::{
+    parameter::TypeParameter,
+    parse_loose,
+    property::{Kind, Property},
+    Error, ParseOptions, Vcard,
+};
 
 #[test]
 fn loose() -> Result<()> {
@@ -22,3 +27,83 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn loose_parse_with_warnings() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+TEL;TYPE=mobile:+1-555-555-5555
+RELATED;TYPE=boss:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+END:VCARD"#;
+
+    // Strict mode rejects the unknown telephone type
+    assert!(matches!(
+        strip_position(Vcard::parse_with(input, Default::default())),
+        Err(Error::UnknownTelephoneType(_))
+    ));
+
+    let options = ParseOptions {
+        strict: false,
+        ..Default::default()
+    };
+    let (card, warnings) = Vcard::parse_with(input, options.clone())?;
+    assert_eq!(3, warnings.len());
+    assert!(matches!(warnings[0].0, Error::UnknownTelephoneType(_)));
+    assert!(matches!(warnings[1].0, Error::UnknownRelatedType(_)));
+    assert!(matches!(warnings[2].0, Error::NoFormattedName));
+
+    let tel = card.tel.first().unwrap();
+    let types = tel.parameters().unwrap().types.as_ref().unwrap();
+    assert_eq!(
+        &TypeParameter::Unknown("mobile".to_string()),
+        types.first().unwrap()
+    );
+    let related = card.related.first().unwrap();
+    let types = related.parameters().unwrap().types.as_ref().unwrap();
+    assert_eq!(
+        &TypeParameter::Unknown("boss".to_string()),
+        types.first().unwrap()
+    );
+
+    // Unknown types are written back as they were given
+    let encoded = card.to_string();
+    assert!(encoded.contains("\r\nTEL;TYPE=mobile:+1-555-555-5555\r\n"));
+    assert!(encoded.contains("\r\nRELATED;TYPE=boss:urn:uuid:"));
+    let (decoded, _) = Vcard::parse_with(&encoded, options)?;
+    assert_eq!(card, decoded);
+    Ok(())
+}
+
+#[test]
+fn loose_skip_invalid_properties() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+ADR;PREF=0:;;123 Main Street;Any Town;CA;91921-1234;U.S.A.
+EMAIL:jane@example.com
+FN:Jane Doe
+END:VCARD"#;
+
+    let options = ParseOptions {
+        skip_invalid_properties: true,
+        ..Default::default()
+    };
+    let (card, skipped) = Vcard::parse_skipping(input, options.clone())?;
+    assert_eq!(1, skipped.len());
+    assert_eq!(3, skipped[0].0);
+    assert!(matches!(skipped[0].1, Error::PrefOutOfRange(0)));
+    assert!(card.address.is_empty());
+    assert_eq!("jane@example.com", card.email[0].value);
+    assert_eq!("Jane Doe", card.formatted_name[0].value);
+
+    // Required properties are still checked
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+ADR;PREF=0:;;123 Main Street;Any Town;CA;91921-1234;U.S.A.
+EMAIL:jane@example.com
+END:VCARD"#;
+    assert!(matches!(
+        Vcard::parse_skipping(input, options),
+        Err(Error::NoFormattedName)
+    ));
+    Ok(())
+}
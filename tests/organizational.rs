@@ -1,9 +1,16 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
 use test_helpers::assert_round_trip;
 use uriparse::uri::URI as Uri;
-use vcard4::{parameter::TypeParameter, parse, property::*};
+use vcard4::{
+    parameter::{RelatedType, TypeParameter},
+    parse,
+    property::*,
+    Error,
+};
 
 #[test]
 fn organizational_title() -> Result<()> {
@@ -83,9 +90,31 @@ END:VCARD"#;
     assert_eq!(1, vcards.len());
 
     let card = vcards.remove(0);
+    let org = &card.org.first().unwrap().value;
+    assert_eq!(Some("ABC, Inc."), org.name.as_deref());
+    assert_eq!(vec!["North American Division", "Marketing"], org.units);
+    assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Mr. John Q. Public\, Esq.
+ORG:ABC Inc.;North American Division;Marketing
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let prop = card.org.first().unwrap();
     assert_eq!(
-        vec!["ABC, Inc.", "North American Division", "Marketing"],
-        card.org.get(0).unwrap().value
+        Organization {
+            name: Some("ABC Inc.".to_owned()),
+            units: vec![
+                "North American Division".to_owned(),
+                "Marketing".to_owned()
+            ],
+        },
+        prop.value
+    );
+    assert_eq!(
+        "ABC Inc.;North American Division;Marketing",
+        prop.to_string()
     );
     assert_round_trip(&card)?;
     Ok(())
@@ -266,3 +295,57 @@ END:VCARD"#;
 
     Ok(())
 }
+
+#[test]
+fn organizational_related_types() -> Result<()> {
+    let types = [
+        "contact",
+        "acquaintance",
+        "friend",
+        "met",
+        "co-worker",
+        "colleague",
+        "co-resident",
+        "neighbor",
+        "child",
+        "parent",
+        "sibling",
+        "spouse",
+        "kin",
+        "muse",
+        "crush",
+        "date",
+        "sweetheart",
+        "me",
+        "agent",
+        "emergency",
+    ];
+    for value in types {
+        let related: RelatedType = value.parse()?;
+        assert_eq!(value, related.to_string());
+    }
+    assert!(matches!(
+        "boss".parse::<RelatedType>(),
+        Err(Error::UnknownRelatedType(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn organizational_member_requires_group() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+MEMBER:urn:uuid:03a0e51f-d1aa-4385-8a53-e29025acd8af
+END:VCARD"#;
+    assert!(matches!(parse(input), Err(Error::MemberRequiresGroup)));
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+KIND:group
+FN:The Doe family
+MEMBER:not a uri
+END:VCARD"#;
+    assert!(parse(input).is_err());
+    Ok(())
+}
@@ -1,6 +1,9 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
+use std::borrow::Cow;
 use uriparse::uri::URI as Uri;
 
 use vcard4::{
@@ -10,9 +13,13 @@ use vcard4::{
         ValueType,
     },
     parse,
+    property::{DateTimeOrTextProperty, Property, TextOrUriProperty},
+    Error, LexError,
 };
 
-use test_helpers::{assert_language, assert_media_type, assert_round_trip};
+use test_helpers::{
+    assert_language, assert_media_type, assert_round_trip, strip_position,
+};
 
 #[test]
 fn parse_parameters() -> Result<()> {
@@ -29,8 +36,8 @@ END:VCARD"#;
     let fname = card.formatted_name.get(0).unwrap();
     assert_eq!("Mr. John Q. Public, Esq.", fname.value);
 
-    let nickname = card.nickname.get(0).unwrap();
-    assert_eq!("Boss", nickname.value);
+    let nickname = card.nickname.first().unwrap();
+    assert_eq!(vec!["Boss"], nickname.value);
     assert!(nickname.parameters.is_some());
 
     //let tag: LanguageTag = "en".parse()?;
@@ -62,6 +69,33 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "language-tags")]
+fn param_language_tag() -> Result<()> {
+    use language_tags::LanguageTag;
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;LANGUAGE=en-US:John
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+    let params = card.formatted_name[0].parameters().unwrap();
+    let expected = LanguageTag::parse("en-US")?;
+    assert_eq!(Some(&expected), params.language());
+    assert_eq!(Some("US"), params.language().unwrap().region());
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;LANGUAGE=en-:John
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::LanguageParse(_))
+    ));
+    Ok(())
+}
+
 #[test]
 fn param_value() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -113,6 +147,33 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+fn param_altid_group() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;ALTID=1;LANGUAGE=ja:山田太郎
+FN;ALTID=1;LANGUAGE=en:Taro Yamada
+FN:Yamada
+NOTE;ALTID=2:Note
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let group = card.altid_group("1");
+    assert_eq!(2, group.len());
+    assert_eq!("Taro Yamada", group[1].to_string());
+    let languages = group
+        .iter()
+        .map(|prop| prop.parameters().unwrap().values("LANGUAGE"))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![vec!["ja"], vec!["en"]], languages);
+
+    assert_eq!(1, card.altid_group("2").len());
+    assert!(card.altid_group("3").is_empty());
+    Ok(())
+}
+
 #[test]
 fn param_pid() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -134,6 +195,21 @@ END:VCARD"#;
         prop.parameters.as_ref().unwrap().pid.as_ref().unwrap()
     );
     assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;PID=1.1,2:jane@example.com
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+    let prop = card.email.first().unwrap();
+    let pids = prop.parameters.as_ref().unwrap().pids();
+    assert_eq!(&[Pid::new(1, Some(1)), Pid::new(2, None)], pids);
+    assert_eq!("1.1", pids[0].to_string());
+    assert!("1.a".parse::<Pid>().is_err());
+    assert_round_trip(&card)?;
     Ok(())
 }
 
@@ -217,6 +293,29 @@ END:VCARD"#;
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "mime")]
+fn param_mediatype_mime() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+PHOTO;MEDIATYPE=image/png:http://www.example.com/pub/photos/jqpublic.png
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+    let params = card.photo[0].parameters().unwrap();
+    assert_eq!(Some(&mime::IMAGE_PNG), params.media_type());
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+PHOTO;MEDIATYPE=image:http://www.example.com/pub/photos/jqpublic.png
+END:VCARD"#;
+    assert!(matches!(strip_position(parse(input)), Err(Error::Mime(_))));
+    Ok(())
+}
+
 #[test]
 fn param_calscale() -> Result<()> {
     let input = r#"BEGIN:VCARD
@@ -239,17 +338,33 @@ END:VCARD"#;
 fn param_sort_as() -> Result<()> {
     let input = r#"BEGIN:VCARD
 VERSION:4.0
-FN;SORT-AS="Doe,Jane":Jane Doe
+FN:Rene van der Harten
+N;SORT-AS="Harten,Rene":van der Harten;Rene,J.;Sir;R.D.O.N.
+ORG;SORT-AS=ABC:ABC\, Inc.;North American Division;Marketing
 END:VCARD"#;
     let mut vcards = parse(input)?;
     assert_eq!(1, vcards.len());
     let card = vcards.remove(0);
-    let prop = card.formatted_name.get(0).unwrap();
+    let prop = card.name.as_ref().unwrap();
     assert_eq!(
-        &vec!["Doe", "Jane"],
-        prop.parameters.as_ref().unwrap().sort_as.as_ref().unwrap()
+        &vec!["Harten", "Rene"],
+        prop.parameters.as_ref().unwrap().sort_as()
     );
+    let prop = card.org.first().unwrap();
+    assert_eq!(&vec!["ABC"], prop.parameters.as_ref().unwrap().sort_as());
+    assert!(card
+        .to_string()
+        .contains("\r\nN;SORT-AS=\"Harten,Rene\":van der Harten;"));
     assert_round_trip(&card)?;
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;SORT-AS="Doe,Jane":Jane Doe
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::InvalidSortAs(_))
+    ));
     Ok(())
 }
 
@@ -327,3 +442,222 @@ END:VCARD"#;
 
     Ok(())
 }
+
+#[test]
+fn param_named_values() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+TEL;TYPE=home,voice;PID=1.1:tel:+1-555-555-5555
+EMAIL;TYPE=work;PID=2:jane@example.com
+X-FOO;X-TAG=a,b:value
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let types = card.parameters_named("type").collect::<Vec<_>>();
+    assert_eq!(vec!["home", "voice", "work"], types);
+
+    let pids = card.parameters_named("PID").collect::<Vec<_>>();
+    assert_eq!(vec!["1.1", "2"], pids);
+
+    let tags = card.parameters_named("x-tag").collect::<Vec<_>>();
+    assert_eq!(vec!["a", "b"], tags);
+    assert!(tags.iter().all(|tag| matches!(tag, Cow::Borrowed(_))));
+
+    assert_eq!(0, card.parameters_named("LANGUAGE").count());
+    Ok(())
+}
+
+#[test]
+fn param_quoted_values() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+X-FOO;P="a;b,c";Q=d,"e:f",g;R="^^x^'y^'^n^z":v
+TEL;TYPE="work,voice":tel:+1-555-555-5555
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let prop = card.extensions.first().unwrap();
+    let extensions = prop
+        .parameters
+        .as_ref()
+        .unwrap()
+        .extensions
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        &vec![
+            ("P".to_owned(), vec!["a;b,c".to_owned()]),
+            (
+                "Q".to_owned(),
+                vec!["d".to_owned(), "e:f".to_owned(), "g".to_owned()]
+            ),
+            ("R".to_owned(), vec!["^x\"y\"\n^z".to_owned()]),
+        ],
+        extensions
+    );
+
+    let prop = card.tel.first().unwrap();
+    assert_eq!(
+        &vec![
+            TypeParameter::Work,
+            TypeParameter::Telephone(TelephoneType::Voice)
+        ],
+        prop.parameters().unwrap().types.as_ref().unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn param_unterminated_quote() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+X-FOO;P=a,"a;b:v
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::LexError(LexError::UnterminatedQuote(2)))
+    ));
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+X-FOO;P="a"b:v
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::DelimiterExpected)
+    ));
+    Ok(())
+}
+
+#[test]
+fn param_caret_encoding() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+ADR;LABEL="123 Main St^nApt ^'B^'^nx^y":;;123 Main St;Any Town;CA;91921;
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let prop = card.address.first().unwrap();
+    let label = prop.parameters.as_ref().unwrap().label.as_ref().unwrap();
+    assert_eq!("123 Main St\nApt \"B\"\nx^y", label);
+
+    let encoded = card.to_string();
+    assert!(encoded.contains(r#"LABEL="123 Main St^nApt ^'B^'^nx^^y""#));
+
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn param_charset() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;CHARSET=utf-8:a@b
+NOTE;charset=UTF-8;TYPE=work:Note
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+    assert!(card.email[0].parameters.is_none());
+    let encoded = card.to_string();
+    assert!(encoded.contains("\r\nEMAIL:a@b\r\n"));
+    assert!(encoded.contains("\r\nNOTE;TYPE=work:Note\r\n"));
+    assert_round_trip(&card)?;
+
+    // Any property left without parameters once CHARSET is
+    // dropped has no parameter set at all
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;CHARSET=UTF-8:Jane Doe
+TEL;CHARSET=UTF-8:+1-555-555-5555
+BDAY;CHARSET=UTF-8:19531015
+X-ABC;CHARSET=UTF-8:Value
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    let card = vcards.remove(0);
+    assert!(card.formatted_name[0].parameters.is_none());
+    assert!(card.tel[0].parameters().is_none());
+    assert!(card.bday.as_ref().unwrap().parameters().is_none());
+    assert!(card.extensions[0].parameters.is_none());
+    assert_eq!(
+        "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nTEL:+1-555-555-5555\r\nBDAY:19531015\r\nX-ABC:Value\r\nEND:VCARD\r\n",
+        card.to_string()
+    );
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;CHARSET=ISO-8859-1:a@b
+END:VCARD"#;
+    assert!(matches!(
+        strip_position(parse(input)),
+        Err(Error::CharsetParameter(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn param_order() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;X-B=2;TYPE=work;X-A=1;PREF=1;PID=1;ALTID=1;LANGUAGE=en;VALUE=text:jane@example.com
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let params = card.email[0].parameters.as_ref().unwrap();
+    assert_eq!(
+        ";VALUE=text;ALTID=\"1\";LANGUAGE=en;PID=1;PREF=1;TYPE=work;X-A=1;X-B=2",
+        params.to_string()
+    );
+
+    let encoded = card.to_string();
+    assert_eq!(encoded, card.to_string());
+    assert_eq!(encoded, parse(&encoded)?.remove(0).to_string());
+    Ok(())
+}
+
+#[test]
+fn param_value_type() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+BDAY;VALUE=text:circa 1800
+TEL;VALUE=uri:tel:+1-555-555-5555
+KEY;VALUE=uri:https://example.com/key.asc
+X-COUNT;VALUE=integer:42
+REV:20230101T120000Z
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+
+    let bday = card.bday.as_ref().unwrap();
+    assert!(matches!(bday, DateTimeOrTextProperty::Text(_)));
+    assert_eq!(ValueType::Text, bday.value_type());
+    assert_eq!("circa 1800", bday.to_string());
+
+    let tel = card.tel.first().unwrap();
+    assert!(matches!(tel, TextOrUriProperty::Uri(_)));
+    assert_eq!(ValueType::Uri, tel.value_type());
+
+    assert_eq!(ValueType::Uri, card.key[0].value_type());
+    assert_eq!(ValueType::Text, card.formatted_name[0].value_type());
+    assert_eq!(ValueType::Integer, card.extensions[0].value_type());
+    assert_eq!(
+        ValueType::Timestamp,
+        card.rev.as_ref().unwrap().value_type()
+    );
+    assert_round_trip(&card)?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::get_first)]
+
 mod test_helpers;
 
 use anyhow::Result;
@@ -49,3 +51,30 @@ END:VCARD"#;
     }
     Ok(())
 }
+
+#[test]
+fn security_key_data_uri() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+KEY:data:application/pgp-keys;base64,LS0tLS1CRUdJTiBQR1AgUFVCTElDIEtF
+ WSBCTE9DSy0tLS0tCm1ETUVaCi0tLS0tRU5EIFBHUCBQVUJMSUMgS0VZIEJMT0NLLS0tLS0K
+KEY;VALUE=uri:https://example.com/keys/jdoe.asc
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert_eq!(2, card.key.len());
+
+    let key = card.key[0].as_data_uri()?.unwrap();
+    assert_eq!(
+        "application/pgp-keys",
+        key.media_type.as_ref().unwrap().to_string()
+    );
+    let data = String::from_utf8(key.data.clone())?;
+    assert!(data.starts_with("-----BEGIN PGP PUBLIC KEY BLOCK-----\n"));
+    assert!(data.ends_with("-----END PGP PUBLIC KEY BLOCK-----\n"));
+
+    assert!(matches!(&card.key[1], TextOrUriProperty::Uri(_)));
+    assert!(card.key[1].as_data_uri()?.is_none());
+    assert_round_trip(&card)?;
+    Ok(())
+}
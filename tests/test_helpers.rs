@@ -1,6 +1,6 @@
 use anyhow::Result;
 
-use vcard4::{parameter::Parameters, parse, Vcard};
+use vcard4::{parameter::Parameters, parse, Error, Vcard};
 
 #[cfg(feature = "language-tags")]
 use language_tags::LanguageTag;
@@ -15,6 +15,15 @@ pub fn assert_round_trip(card: &Vcard) -> Result<()> {
     Ok(())
 }
 
+/// Remove the position from a parse error.
+#[allow(dead_code)]
+pub fn strip_position<T>(result: vcard4::Result<T>) -> vcard4::Result<T> {
+    result.map_err(|e| match e {
+        Error::At { source, .. } => *source,
+        e => e,
+    })
+}
+
 #[cfg(feature = "serde")]
 #[allow(dead_code)]
 pub fn assert_serde_round_trip(card: &Vcard) -> Result<()> {
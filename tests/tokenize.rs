@@ -0,0 +1,94 @@
+use anyhow::Result;
+use vcard4::{tokenize, Error, LexError, Token};
+
+#[test]
+fn tokenize_property() -> Result<()> {
+    let tokens = tokenize("FN:John")?;
+    assert_eq!(
+        vec![
+            Token::PropertyName(0..2),
+            Token::ValueSeparator(2..3),
+            Token::Value(3..7),
+        ],
+        tokens
+    );
+    Ok(())
+}
+
+#[test]
+fn tokenize_parameters() -> Result<()> {
+    let input = "BEGIN:VCARD\r\nitem1.TEL;TYPE=\"voice,home\";PREF=1:tel:+1\r\n folded\r\nEND:VCARD\r\n";
+    let tokens = tokenize(input)?;
+    let text = |token: &Token| -> String {
+        let range = match token {
+            Token::Group(range)
+            | Token::GroupSeparator(range)
+            | Token::PropertyName(range)
+            | Token::ParamName(range)
+            | Token::ParamValue(range)
+            | Token::ValueSeparator(range)
+            | Token::Value(range)
+            | Token::Newline(range) => range.clone(),
+        };
+        input[range].to_string()
+    };
+    assert_eq!(
+        vec![
+            "BEGIN",
+            ":",
+            "VCARD",
+            "\r\n",
+            "item1",
+            ".",
+            "TEL",
+            "TYPE",
+            "\"voice,home\"",
+            "PREF",
+            "1",
+            ":",
+            "tel:+1\r\n folded",
+            "\r\n",
+            "END",
+            ":",
+            "VCARD",
+            "\r\n",
+        ],
+        tokens.iter().map(text).collect::<Vec<_>>()
+    );
+    assert!(matches!(tokens[7], Token::ParamName(_)));
+    assert!(matches!(tokens[8], Token::ParamValue(_)));
+
+    assert!(matches!(tokenize("FN"), Err(Error::DelimiterExpected)));
+    assert!(matches!(
+        tokenize("FN;X-A=a,\"open:John"),
+        Err(Error::LexError(LexError::UnterminatedQuote(2)))
+    ));
+    assert!(matches!(
+        tokenize("FN:Jo\x0Bhn"),
+        Err(Error::LexError(LexError::UnexpectedControlChar('\x0B', 2)))
+    ));
+    Ok(())
+}
+
+#[test]
+fn tokenize_byte_order_mark() -> Result<()> {
+    let tokens = tokenize("\u{FEFF}FN:John\rNOTE:x")?;
+    assert_eq!(
+        vec![
+            Token::PropertyName(3..5),
+            Token::ValueSeparator(5..6),
+            Token::Value(6..10),
+            Token::Newline(10..11),
+            Token::PropertyName(11..15),
+            Token::ValueSeparator(15..16),
+            Token::Value(16..17),
+        ],
+        tokens
+    );
+
+    assert!(matches!(
+        tokenize("a_b.FN:John"),
+        Err(Error::InvalidGroup(group)) if group == "a_b"
+    ));
+    Ok(())
+}
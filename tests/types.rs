@@ -1,7 +1,12 @@
+#![allow(clippy::unnecessary_fallible_conversions)]
+
 use anyhow::Result;
 use proptest::prelude::*;
 use time::UtcOffset;
-use vcard4::{helper::*, property::DateAndOrTime};
+use vcard4::{
+    helper::*,
+    property::{DateAndOrTime, PartialDate, TimePrecision},
+};
 
 #[test]
 fn types_time_only() -> Result<()> {
@@ -90,21 +95,30 @@ fn types_date_and_or_time() -> Result<()> {
     }
 
     let value: DateAndOrTime = "--1022T1400".parse()?;
-    if let DateAndOrTime::DateTime(value) = value {
+    if let DateAndOrTime::ReducedDateTime(value, TimePrecision::Minute) =
+        value
+    {
         //let value = value.get(0).unwrap();
         assert_eq!("0000-10-22 14:00:00.0 +00:00:00", &value.to_string());
     } else {
-        panic!("expecting DateTime variant");
+        panic!("expecting ReducedDateTime variant");
     }
 
     let value: DateAndOrTime = "---22T14".parse()?;
-    if let DateAndOrTime::DateTime(value) = value {
+    if let DateAndOrTime::ReducedDateTime(value, TimePrecision::Hour) = value
+    {
         //let value = value.get(0).unwrap();
         assert_eq!("0000-01-22 14:00:00.0 +00:00:00", &value.to_string());
     } else {
-        panic!("expecting DateTime variant");
+        panic!("expecting ReducedDateTime variant");
     }
 
+    // Reduced accuracy is kept when writing the value
+    let value: DateAndOrTime = "20090808T1430-0500".parse()?;
+    assert_eq!("20090808T1430-0500", value.to_string());
+    let value: DateAndOrTime = "20090808T14Z".parse()?;
+    assert_eq!("20090808T14Z", value.to_string());
+
     let value: DateAndOrTime = "19850412".parse()?;
     if let DateAndOrTime::Date(value) = value {
         //let value = value.get(0).unwrap();
@@ -114,37 +128,64 @@ fn types_date_and_or_time() -> Result<()> {
     }
 
     let value: DateAndOrTime = "1985-04".parse()?;
-    if let DateAndOrTime::Date(value) = value {
-        //let value = value.get(0).unwrap();
-        assert_eq!("1985-04-01", &value.to_string());
+    if let DateAndOrTime::PartialDate(date) = &value {
+        assert_eq!(Some(1985), date.year());
+        assert_eq!(Some(4), date.month());
+        assert_eq!(None, date.day());
+        assert_eq!("1985-04", &value.to_string());
     } else {
-        panic!("expecting Date variant");
+        panic!("expecting PartialDate variant");
     }
 
     let value: DateAndOrTime = "1985".parse()?;
-    if let DateAndOrTime::Date(value) = value {
-        //let value = value.get(0).unwrap();
-        assert_eq!("1985-01-01", &value.to_string());
+    if let DateAndOrTime::PartialDate(date) = &value {
+        assert_eq!(Some(1985), date.year());
+        assert_eq!(None, date.month());
+        assert_eq!(None, date.day());
+        assert_eq!("1985", &value.to_string());
     } else {
-        panic!("expecting Date variant");
+        panic!("expecting PartialDate variant");
     }
 
     let value: DateAndOrTime = "--0412".parse()?;
-    if let DateAndOrTime::Date(value) = value {
-        //let value = value.get(0).unwrap();
-        assert_eq!("0000-04-12", &value.to_string());
+    if let DateAndOrTime::PartialDate(date) = &value {
+        assert_eq!(None, date.year());
+        assert_eq!(Some(4), date.month());
+        assert_eq!(Some(12), date.day());
+        assert_eq!("--0412", &value.to_string());
     } else {
-        panic!("expecting Date variant");
+        panic!("expecting PartialDate variant");
     }
 
     let value: DateAndOrTime = "---12".parse()?;
-    if let DateAndOrTime::Date(value) = value {
-        //let value = value.get(0).unwrap();
-        assert_eq!("0000-01-12", &value.to_string());
+    if let DateAndOrTime::PartialDate(date) = &value {
+        assert_eq!(None, date.year());
+        assert_eq!(None, date.month());
+        assert_eq!(Some(12), date.day());
+        assert_eq!("---12", &value.to_string());
     } else {
-        panic!("expecting Date variant");
+        panic!("expecting PartialDate variant");
     }
 
+    let value: DateAndOrTime = "--04".parse()?;
+    if let DateAndOrTime::PartialDate(date) = &value {
+        assert_eq!(None, date.year());
+        assert_eq!(Some(4), date.month());
+        assert_eq!(None, date.day());
+        assert_eq!("--04", &value.to_string());
+    } else {
+        panic!("expecting PartialDate variant");
+    }
+
+    let value: DateAndOrTime = "--0229".parse()?;
+    assert_eq!("--0229", &value.to_string());
+
+    assert!("--1302".parse::<DateAndOrTime>().is_err());
+    assert!("--0230".parse::<DateAndOrTime>().is_err());
+    assert!("---32".parse::<DateAndOrTime>().is_err());
+    assert!("19a5".parse::<DateAndOrTime>().is_err());
+    assert!(PartialDate::new(Some(1985), None, Some(12)).is_err());
+
     let value: DateAndOrTime = "T102200".parse()?;
     if let DateAndOrTime::Time(value) = value {
         //let value = value.get(0).unwrap();
@@ -1,5 +1,10 @@
 use anyhow::Result;
-use vcard4::Vcard;
+use vcard4::{
+    parameter::{Pid, TypeParameter},
+    parse,
+    property::{Kind, KindProperty},
+    Error, Vcard, VcardBuilder,
+};
 
 #[test]
 fn validate() -> Result<()> {
@@ -7,3 +12,67 @@ fn validate() -> Result<()> {
     assert!(card.validate().is_err());
     Ok(())
 }
+
+#[test]
+fn validate_all() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+EMAIL;PID=1.1:jane@example.com
+CLIENTPIDMAP:1;urn:uuid:3df403f4-5924-4bb7-b077-3c711d9eb34b
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert!(card.validate_all().is_empty());
+
+    let mut card = VcardBuilder::new("Mock Company".to_owned())
+        .member("https://example.com/foo".try_into()?)
+        .email("jane@example.com".to_owned())
+        .finish();
+    card.formatted_name.clear();
+    card.email[0]
+        .parameters
+        .get_or_insert_with(Default::default)
+        .pid = Some(vec![Pid::new(1, Some(2))]);
+
+    let errors = card.validate_all();
+    assert_eq!(3, errors.len());
+    assert!(matches!(errors[0], Error::NoFormattedName));
+    assert!(matches!(errors[1], Error::MemberRequiresGroup));
+    assert!(matches!(&errors[2], Error::InvalidPid(pid) if pid == "1.2"));
+    Ok(())
+}
+
+#[test]
+fn validate_type_parameter() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;TYPE=work:Jane Doe
+EMAIL;TYPE=work:jane@example.com
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    assert!(card.validate_all().is_empty());
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+BDAY;TYPE=work:19850412
+END:VCARD"#;
+    assert!(matches!(
+        parse(input),
+        Err(Error::At { source, .. })
+            if matches!(&*source, Error::TypeParameter(name) if name == "BDAY")
+    ));
+
+    let mut card = VcardBuilder::new("Jane Doe".to_owned()).finish();
+    let mut kind: KindProperty = Kind::Individual.into();
+    kind.parameters.get_or_insert_with(Default::default).types =
+        Some(vec![TypeParameter::Work]);
+    card.kind = Some(kind);
+
+    let errors = card.validate_all();
+    assert_eq!(1, errors.len());
+    assert!(
+        matches!(&errors[0], Error::TypeParameter(name) if name == "KIND")
+    );
+    Ok(())
+}
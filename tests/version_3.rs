@@ -1,7 +1,7 @@
 mod test_helpers;
 
 use anyhow::Result;
-use vcard4::parse;
+use vcard4::{parse, Error, Version};
 
 use test_helpers::assert_round_trip;
 
@@ -22,3 +22,130 @@ END:VCARD"#;
     assert_round_trip(&card)?;
     Ok(())
 }
+
+#[test]
+fn parse_version3_translate() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:3.0
+FN:Jane Doe
+N:Doe;Jane;;;
+TEL;HOME;VOICE:+1-555-555-5555
+EMAIL;TYPE=INTERNET,HOME,PREF:jane@example.com
+EMAIL;TYPE=X400:/c=us/a=attmail/o=example/s=doe/
+GEO:37.386013;-122.082932
+AGENT;VALUE=uri:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    assert_eq!(1, vcards.len());
+    let card = vcards.remove(0);
+
+    let expected = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+N:Doe;Jane;;;
+TEL;TYPE=home,voice:+1-555-555-5555
+EMAIL;PREF=1;TYPE=home:jane@example.com
+EMAIL;TYPE=X400:/c=us/a=attmail/o=example/s=doe/
+GEO:geo:37.386013,-122.082932
+RELATED;VALUE=uri;TYPE=agent:urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6
+END:VCARD
+"#;
+    assert_eq!(expected.replace('\n', "\r\n"), card.to_string());
+    let mut vcards = parse(expected)?;
+    let expected = vcards.remove(0);
+
+    assert_eq!(expected, card);
+    assert_eq!(
+        (37.386013, -122.082932),
+        card.geo.first().unwrap().coordinates()?.lat_lon()
+    );
+    assert_round_trip(&card)?;
+    Ok(())
+}
+
+#[test]
+fn parse_version_unsupported() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:2.1
+FN:Jane Doe
+END:VCARD"#;
+    assert!(matches!(
+        parse(input),
+        Err(Error::UnsupportedVersion(version)) if version == "2.1"
+    ));
+    assert_eq!(Version::V3, "3.0".parse::<Version>()?);
+    assert_eq!("4.0", Version::V4.to_string());
+    Ok(())
+}
+
+#[test]
+fn write_version3() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+KIND:individual
+FN:Jane Doe
+TEL;TYPE=home,voice;PREF=1:+1-555-555-5555
+GEO:geo:37.386013,-122.082932
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    let card = vcards.remove(0);
+
+    let expected = "BEGIN:VCARD\r\nVERSION:3.0\r\nN:;;;;\r\nFN:Jane Doe\r\nTEL;TYPE=HOME,VOICE,PREF:+1-555-555-5555\r\nGEO:37.386013;-122.082932\r\nEND:VCARD\r\n";
+    let output = card.to_version(Version::V3)?;
+    assert_eq!(expected, output);
+    assert_eq!(card.to_string(), card.to_version(Version::V4)?);
+
+    let mut vcards = parse(&output)?;
+    let legacy = vcards.remove(0);
+    assert_eq!(card.geo, legacy.geo);
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+N:Doe;Jane;;;
+BDAY;CALSCALE=gregorian:19531015
+PHOTO;MEDIATYPE=image/jpeg:http://www.example.com/pub/photos/jdoe.jpg
+item1.ADR;TYPE=work;PREF=1;LABEL="Mr. John Q. Public, Esq.\nMail Drop: TNE QB\n123 Main Street":;;123 Main Street;;;;
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    let card = vcards.remove(0);
+    let expected = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nN:Doe;Jane;;;\r\nBDAY:19531015\r\nPHOTO;TYPE=JPEG:http://www.example.com/pub/photos/jdoe.jpg\r\nitem1.ADR;TYPE=WORK,PREF:;;123 Main Street;;;;\r\nitem1.LABEL;TYPE=WORK,PREF:Mr. John Q. Public\\, Esq.\\nMail Drop: TNE QB\\n12\r\n 3 Main Street\r\nEND:VCARD\r\n";
+    assert_eq!(expected, card.to_version(Version::V3)?);
+
+    // Parameters that only exist in 4.0 cannot be carried over
+    for (line, param) in [
+        ("FN;ALTID=1:Jane Doe", "FN;ALTID"),
+        ("FN;PID=1:Jane Doe", "FN;PID"),
+        ("N;SORT-AS=\"Doe,Jane\":Doe;Jane;;;", "N;SORT-AS"),
+        ("BDAY;CALSCALE=x-lunar:19531015", "BDAY;CALSCALE"),
+        ("ADR;GEO=\"geo:12.3457,78.910\":;;1 Main St;;;;", "ADR;GEO"),
+        ("ADR;TZ=-0500:;;1 Main St;;;;", "ADR;TZ"),
+        (
+            "URL;MEDIATYPE=text/html:http://example.com",
+            "URL;MEDIATYPE",
+        ),
+    ] {
+        let input = format!(
+            "BEGIN:VCARD\nVERSION:4.0\nFN:Jane Doe\n{}\nEND:VCARD",
+            line
+        );
+        let card = parse(input)?.remove(0);
+        assert!(matches!(
+            card.to_version(Version::V3),
+            Err(Error::Unrepresentable(name)) if name == param
+        ));
+    }
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Jane Doe
+LANG:en
+END:VCARD"#;
+    let mut vcards = parse(input)?;
+    let card = vcards.remove(0);
+    assert!(matches!(
+        card.to_version(Version::V3),
+        Err(Error::Unrepresentable(name)) if name == "LANG"
+    ));
+    Ok(())
+}
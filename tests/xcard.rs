@@ -0,0 +1,145 @@
+use anyhow::Result;
+use vcard4::parse;
+
+/// Remove the whitespace between elements.
+///
+/// Line breaks inside text content are kept.
+fn compact(xml: &str) -> String {
+    let mut out = String::new();
+    for line in xml.lines() {
+        if out.is_empty() || out.ends_with('>') {
+            out.push_str(line.trim());
+        } else {
+            out.push('\n');
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+#[test]
+fn xcard_rfc_example() -> Result<()> {
+    // https://www.rfc-editor.org/rfc/rfc6351#section-4
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Simon Perreault
+N:Perreault;Simon;;;ing. jr,M.Sc.
+BDAY:--0203
+ANNIVERSARY:20090808T1430-0500
+GENDER:M
+LANG;PREF=1:fr
+LANG;PREF=2:en
+ORG;TYPE=work:Viagenie
+ADR;TYPE=work;LABEL="Simon Perreault^n2875 boul. Laurier, suite D2-630^nQuebec, QC, Canada G1V 2M2":;;2875 boul. Laurier\, suite D2-630;Quebec;QC;G1V 2M2;Canada
+TEL;VALUE=uri;TYPE="work,voice":tel:+1-418-656-9254;ext=102
+TEL;VALUE=uri;TYPE="work,text,voice,cell,video":tel:+1-418-262-6501
+EMAIL;TYPE=work:simon.perreault@viagenie.ca
+GEO;TYPE=work:geo:46.766336,-71.28955
+KEY;TYPE=work;VALUE=uri:http://www.viagenie.ca/simon.perreault/simon.asc
+TZ:America/Montreal
+URL;TYPE=home:http://nomis80.org
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<vcards xmlns="urn:ietf:params:xml:ns:vcard-4.0">
+  <vcard>
+    <fn><text>Simon Perreault</text></fn>
+    <n>
+      <surname>Perreault</surname>
+      <given>Simon</given>
+      <additional/>
+      <prefix/>
+      <suffix>ing. jr</suffix>
+      <suffix>M.Sc.</suffix>
+    </n>
+    <bday><date>--0203</date></bday>
+    <anniversary>
+      <date-time>20090808T1430-0500</date-time>
+    </anniversary>
+    <gender><sex>M</sex></gender>
+    <lang>
+      <parameters><pref><integer>1</integer></pref></parameters>
+      <language-tag>fr</language-tag>
+    </lang>
+    <lang>
+      <parameters><pref><integer>2</integer></pref></parameters>
+      <language-tag>en</language-tag>
+    </lang>
+    <org>
+      <parameters><type><text>work</text></type></parameters>
+      <text>Viagenie</text>
+    </org>
+    <adr>
+      <parameters>
+        <type><text>work</text></type>
+        <label><text>Simon Perreault
+2875 boul. Laurier, suite D2-630
+Quebec, QC, Canada G1V 2M2</text></label>
+      </parameters>
+      <pobox/>
+      <ext/>
+      <street>2875 boul. Laurier, suite D2-630</street>
+      <locality>Quebec</locality>
+      <region>QC</region>
+      <code>G1V 2M2</code>
+      <country>Canada</country>
+    </adr>
+    <tel>
+      <parameters>
+        <type>
+          <text>work</text>
+          <text>voice</text>
+        </type>
+      </parameters>
+      <uri>tel:+1-418-656-9254;ext=102</uri>
+    </tel>
+    <tel>
+      <parameters>
+        <type>
+          <text>work</text>
+          <text>text</text>
+          <text>voice</text>
+          <text>cell</text>
+          <text>video</text>
+        </type>
+      </parameters>
+      <uri>tel:+1-418-262-6501</uri>
+    </tel>
+    <email>
+      <parameters><type><text>work</text></type></parameters>
+      <text>simon.perreault@viagenie.ca</text>
+    </email>
+    <geo>
+      <parameters><type><text>work</text></type></parameters>
+      <uri>geo:46.766336,-71.28955</uri>
+    </geo>
+    <key>
+      <parameters><type><text>work</text></type></parameters>
+      <uri>http://www.viagenie.ca/simon.perreault/simon.asc</uri>
+    </key>
+    <tz><text>America/Montreal</text></tz>
+    <url>
+      <parameters><type><text>home</text></type></parameters>
+      <uri>http://nomis80.org</uri>
+    </url>
+  </vcard>
+</vcards>"#;
+    assert_eq!(compact(expected), card.to_xcard());
+    Ok(())
+}
+
+#[test]
+fn xcard_group_escape() -> Result<()> {
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN:Smith & Sons <Ltd>
+item1.EMAIL:info@example.com
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    let xml = card.to_xcard();
+    assert!(
+        xml.contains("<fn><text>Smith &amp; Sons &lt;Ltd&gt;</text></fn>")
+    );
+    assert!(xml.contains(r#"<group name="item1"><email><text>info@example.com</text></email></group>"#));
+    Ok(())
+}
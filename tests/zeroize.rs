@@ -0,0 +1,90 @@
+#![cfg(feature = "zeroize")]
+
+use anyhow::Result;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+use vcard4::{parse, property::UriProperty};
+
+const SECRET: &[u8] = b"correct-horse-battery-staple";
+
+static ARMED: AtomicBool = AtomicBool::new(false);
+static LEAKED: AtomicBool = AtomicBool::new(false);
+
+/// Tests share the allocator state so they must not overlap.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Allocator that records whether a block still holds
+/// the secret when it is freed.
+struct ScanAllocator;
+
+unsafe impl GlobalAlloc for ScanAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ARMED.load(Ordering::SeqCst) {
+            let block = std::slice::from_raw_parts(ptr, layout.size());
+            if block.windows(SECRET.len()).any(|value| value == SECRET) {
+                LEAKED.store(true, Ordering::SeqCst);
+            }
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: ScanAllocator = ScanAllocator;
+
+#[test]
+fn zeroize_data_uri() -> Result<()> {
+    let _guard = LOCK.lock().unwrap();
+
+    // Check the allocator sees a secret that is not wiped
+    ARMED.store(true, Ordering::SeqCst);
+    drop(SECRET.to_vec());
+    ARMED.store(false, Ordering::SeqCst);
+    assert!(LEAKED.swap(false, Ordering::SeqCst));
+
+    let prop: UriProperty =
+        "data:,correct-horse-battery-staple".try_into()?;
+    ARMED.store(true, Ordering::SeqCst);
+    let data = prop.as_data_uri()?.unwrap();
+    assert_eq!(SECRET, &data.data[..]);
+    drop(data);
+    ARMED.store(false, Ordering::SeqCst);
+    assert!(!LEAKED.load(Ordering::SeqCst));
+
+    // Dropping the property wipes the URI text
+    ARMED.store(true, Ordering::SeqCst);
+    drop(prop);
+    ARMED.store(false, Ordering::SeqCst);
+    assert!(!LEAKED.load(Ordering::SeqCst));
+    Ok(())
+}
+
+#[test]
+fn zeroize_parameters() -> Result<()> {
+    let _guard = LOCK.lock().unwrap();
+
+    let input = r#"BEGIN:VCARD
+VERSION:4.0
+FN;ALTID=correct-horse-battery-staple:Jane Doe
+N;SORT-AS="correct-horse-battery-staple":Doe;Jane;;;
+NOTE;TYPE=correct-horse-battery-staple:Note
+NOTE;X-PASSWORD=correct-horse-battery-staple:Note
+BDAY;CALSCALE=x-correct-horse-battery-staple:19531015
+ADR;LABEL="correct-horse-battery-staple":;;1 Main St;;;;
+END:VCARD"#;
+    let card = parse(input)?.remove(0);
+    ARMED.store(true, Ordering::SeqCst);
+    drop(card);
+    ARMED.store(false, Ordering::SeqCst);
+    assert!(!LEAKED.load(Ordering::SeqCst));
+    Ok(())
+}